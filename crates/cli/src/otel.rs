@@ -2,7 +2,7 @@
 /// grpc_server_max_recv_msg_size: 4194304 (4MB) -- Max gRPC message size that can be received
 /// grpc_server_max_send_msg_size: 4194304 (4MB) -- Max gRPC message size that can be sent
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "env", derive(ground_env::FromEnv))]
+#[cfg_attr(feature = "env", derive(ground_env::FromEnv, ground_env::ToEnv))]
 #[cfg_attr(feature = "clap", derive(clap::Parser))]
 pub struct OpentelemetryArgs {
     #[cfg_attr(feature = "clap", clap(
@@ -18,10 +18,11 @@ pub struct OpentelemetryArgs {
         long,
         env,
         default_value = "3s",
-        help = "Otel: timeout for the exporter (using humantime::Duration)"
+        value_parser = ground_env::formats::duration,
+        help = "Otel: timeout for the exporter (such as 3s or 500ms, or a whole number of seconds)"
     ))]
-    #[cfg_attr(feature = "env", env(default = "3s"))]
-    pub otel_timeout: humantime::Duration,
+    #[cfg_attr(feature = "env", env(parse_with = "ground_env::formats::duration", default = "3s"))]
+    pub otel_timeout: std::time::Duration,
 
     #[cfg_attr(feature = "clap", clap(
         long,
@@ -74,7 +75,7 @@ impl Default for OpentelemetryArgs {
     fn default() -> Self {
         Self {
             otel_endpoint: "http://127.0.0.1:4317".to_string(),
-            otel_timeout: std::time::Duration::from_secs(3).into(),
+            otel_timeout: std::time::Duration::from_secs(3),
             otel_max_events_per_span: 128,
             otel_max_attributes_per_span: 128,
             otel_max_attributes_per_event: 128,
@@ -103,7 +104,7 @@ impl OpentelemetryArgs {
         let exporter = opentelemetry_otlp::new_exporter()
             .tonic()
             .with_endpoint(self.otel_endpoint.to_owned())
-            .with_timeout(self.otel_timeout.into());
+            .with_timeout(self.otel_timeout);
 
         let trace_config = trace::config()
             .with_sampler(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
@@ -5,7 +5,6 @@
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use clap::ValueEnum;
 use secrecy::{ExposeSecret, SecretString};
 use sqlx::postgres::PgConnectOptions;
 use sqlx::{ConnectOptions, PgPool};
@@ -51,10 +50,11 @@ Each of the URI parts is optional.
     postgresql://localhost?dbname=mydb&user=postgres&password=postgres
 "###;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 #[cfg_attr(feature = "clap", derive(clap::Parser))]
-#[cfg_attr(feature = "env", derive(ground_env::FromEnv))]
-#[cfg_attr(feature = "env", clap(next_help_heading = "POSTGRES", term_width = 200))]
+#[cfg_attr(feature = "env", derive(ground_env::FromEnv, ground_env::ToEnv))]
+#[cfg_attr(feature = "env", env(prefix = "POSTGRES_", validate_with = "Self::check"))]
+#[cfg_attr(feature = "clap", clap(next_help_heading = "POSTGRES", term_width = 200))]
 pub struct PostgresArgs {
     /// The general form for a connection URI is:
     ///
@@ -92,7 +92,6 @@ pub struct PostgresArgs {
     /// ```
     ///
     #[cfg_attr(feature = "clap", clap(name = "postgres-uri", env = "POSTGRES_URI", long, display_order = 0, help = DB_URI_SHORT_HELP, long_help = DB_URI_LONG_HELP))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_URI"))]
     pub uri: Option<String>,
 
     /// Sets the name of the host to connect to.
@@ -107,7 +106,6 @@ pub struct PostgresArgs {
         long,
         conflicts_with = "postgres-uri"
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_HOST"))]
     pub host: String,
 
     /// Sets the username to connect as.
@@ -118,10 +116,12 @@ pub struct PostgresArgs {
         long,
         conflicts_with = "postgres-uri"
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_USER", default = "postgres"))]
+    #[cfg_attr(feature = "env", env(default = "postgres"))]
     pub user: String,
 
     /// Sets the password to use if the server demands password authentication.
+    ///
+    /// Can also be read from the file named by `POSTGRES_PASSWORD_FILE`.
     #[cfg_attr(feature = "clap", clap(
         name = "postgres-password",
         env = "POSTGRES_PASSWORD",
@@ -129,7 +129,7 @@ pub struct PostgresArgs {
         long,
         conflicts_with = "postgres-uri"
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_PASSWORD", default = "password"))]
+    #[cfg_attr(feature = "env", env(default = "password", file_fallback, expose_secret, secret))]
     pub password: SecretString,
 
     /// Sets the port to connect to at the server host.
@@ -140,7 +140,7 @@ pub struct PostgresArgs {
         long,
         conflicts_with = "postgres-uri"
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_PORT", default = "5432"))]
+    #[cfg_attr(feature = "env", env(default = "5432"))]
     pub port: u16,
 
     /// Sets the database name
@@ -151,13 +151,12 @@ pub struct PostgresArgs {
         long,
         conflicts_with = "postgres-uri"
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_DATABASE", default = "postgres"))]
+    #[cfg_attr(feature = "env", env(default = "postgres"))]
     pub database: String,
 
     /// Sets the schema name. In postgres terms, this is the search path.
     /// Does not conflict with postgres-uri.
     #[cfg_attr(feature = "clap", clap(name = "postgres-schema", env = "POSTGRES_SCHEMA", long))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_SCHEMA"))]
     pub schema: Option<String>,
 
     /// Sets whether or with what priority a secure SSL TCP/IP connection will be negotiated
@@ -176,7 +175,7 @@ pub struct PostgresArgs {
         conflicts_with = "postgres-uri",
         value_enum
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_SSL_MODE", default = "prefer"))]
+    #[cfg_attr(feature = "env", env(default = "prefer"))]
     pub ssl_mode: PgSslMode,
 
     /// Sets the name of a file containing SSL certificate authority (CA) certificate(s).
@@ -190,7 +189,6 @@ pub struct PostgresArgs {
         conflicts_with = "postgres-uri",
         value_hint = clap::ValueHint::FilePath,
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_SSL_ROOT_CERT"))]
     pub ssl_root_cert: Option<PathBuf>,
 
     /// Enables logging of statements.
@@ -202,7 +200,7 @@ pub struct PostgresArgs {
         display_order = 2002,
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_ENABLE_STATEMENT_LOGGING", default))]
+    #[cfg_attr(feature = "env", env(parse_with = "ground_env::formats::bool", default))]
     pub enable_statement_logging: bool,
 
     /// If true, the health of a connection will be verified by a call to Connection::ping
@@ -213,7 +211,7 @@ pub struct PostgresArgs {
         default_value = "false",
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_TEST_BEFORE_ACQUIRE", default))]
+    #[cfg_attr(feature = "env", env(parse_with = "ground_env::formats::bool", default))]
     pub test_before_acquire: bool,
 
     /// Set the maximum number of connections that this pool should maintain.
@@ -227,7 +225,7 @@ pub struct PostgresArgs {
         default_value = "10",
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_MAX_CONNECTIONS", default = "10"))]
+    #[cfg_attr(feature = "env", env(default = "10", range(min = 1)))]
     pub max_connections: u32,
 
     /// Set the maximum amount of time to spend waiting for a connection in Pool::acquire().
@@ -242,13 +240,22 @@ pub struct PostgresArgs {
     /// - If a new connection needs to be opened, that will obviously require I/O, handshaking,
     ///   and initialization commands.
     ///   - If after_connect is set, that will also be executed.
+    ///
+    /// The misspelled POSTGRES_ACQURIE_TIMEOUT_SECONDS is still accepted in place of
+    /// POSTGRES_ACQUIRE_TIMEOUT_SECONDS, with a deprecation warning when read through `FromEnv`.
     #[cfg_attr(feature = "clap", clap(
         name = "acquire-timeout-seconds",
-        env = "POSTGRES_ACQURIE_TIMEOUT_SECONDS",
-        default_value = "30",
+        env = "POSTGRES_ACQUIRE_TIMEOUT_SECONDS",
+        default_value = acquire_timeout_default(),
+        value_parser = parse_seconds,
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_ACQURIE_TIMEOUT_SECONDS", default = "30"))]
+    #[cfg_attr(feature = "env", env(
+        rename = "ACQUIRE_TIMEOUT_SECONDS",
+        deprecated = "ACQURIE_TIMEOUT_SECONDS",
+        parse_with = "parse_seconds",
+        default = "30"
+    ))]
     pub acquire_timeout: u64,
 
     /// Set the minimum number of connections to maintain at all times.
@@ -273,7 +280,7 @@ pub struct PostgresArgs {
         default_value_t = 1,
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_MIN_CONNECTIONS", default = "1"))]
+    #[cfg_attr(feature = "env", env(default = "1"))]
     pub min_connections: u32,
 
     /// Set the maximum lifetime of individual connections.
@@ -290,9 +297,10 @@ pub struct PostgresArgs {
         name = "max-lifetime-seconds",
         env = "POSTGRES_MAX_LIFETIME_SECONDS",
         default_value = "1800",
+        value_parser = parse_seconds,
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_MAX_LIFETIME_SECONDS", default = "1800"))]
+    #[cfg_attr(feature = "env", env(rename = "MAX_LIFETIME_SECONDS", parse_with = "parse_seconds", default = "1800"))]
     pub max_lifetime: u64,
 
     /// Set a maximum idle duration for individual connections.
@@ -304,14 +312,18 @@ pub struct PostgresArgs {
         name = "idle-timeout-seconds",
         env = "POSTGRES_IDLE_TIMEOUT_SECONDS",
         default_value = "600",
+        value_parser = parse_seconds,
         long
     ))]
-    #[cfg_attr(feature = "env", env(rename = "POSTGRES_IDLE_TIMEOUT_SECONDS", default = "600"))]
+    #[cfg_attr(feature = "env", env(rename = "IDLE_TIMEOUT_SECONDS", parse_with = "parse_seconds", default = "600"))]
     pub idle_timeout: u64,
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "env", derive(ground_env::EnvEnum))]
 pub enum PgSslMode {
+    #[cfg_attr(feature = "env", env(alias = "disabled"))]
     Disable,
     Allow,
     Prefer,
@@ -333,29 +345,87 @@ impl From<PgSslMode> for sqlx::postgres::PgSslMode {
     }
 }
 
-#[derive(Debug, thiserror::Error)]
-#[error("Unknown PgSslMode variant: {0}")]
-pub struct UnknownPgSslMode(String);
-
-impl FromStr for PgSslMode {
-    type Err = UnknownPgSslMode;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_ref() {
-            "disabled" => Ok(Self::Disable),
-            "allow" => Ok(Self::Allow),
-            "prefer" => Ok(Self::Prefer),
-            "require" => Ok(Self::Require),
-            "verifyca" => Ok(Self::VerifyCa),
-            "verifyfull" => Ok(Self::VerifyFull),
-            _ => Err(UnknownPgSslMode(s.into())),
-        }
+/// Reads a whole number of seconds, or a duration with units such as `10m`, for both clap and `FromEnv`.
+///
+/// Durations below a whole second are rejected, rather than truncated.
+#[cfg(any(feature = "clap", feature = "env"))]
+fn parse_seconds(input: &str) -> Result<u64, String> {
+    let duration = ground_env::formats::duration(input).map_err(|err| err.to_string())?;
+    if duration.subsec_nanos() != 0 {
+        return Err(String::from("must be a whole number of seconds"));
     }
+    Ok(duration.as_secs())
+}
+
+/// The default of `acquire_timeout` for clap, which is the misspelled POSTGRES_ACQURIE_TIMEOUT_SECONDS if it's set,
+/// mirroring `deprecated` on the `FromEnv` side.
+#[cfg(feature = "clap")]
+fn acquire_timeout_default() -> &'static str {
+    static DEFAULT: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    DEFAULT.get_or_init(|| {
+        std::env::var("POSTGRES_ACQURIE_TIMEOUT_SECONDS").unwrap_or_else(|_| String::from("30"))
+    })
 }
 
 static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
 
+/// Prints the password as `[REDACTED]`, and masks the password of the URI.
+impl std::fmt::Debug for PostgresArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ground_env::redact::{mask_url_credentials, REDACTED};
+
+        let PostgresArgs {
+            uri,
+            host,
+            user,
+            password: _,
+            port,
+            database,
+            schema,
+            ssl_mode,
+            ssl_root_cert,
+            enable_statement_logging,
+            test_before_acquire,
+            max_connections,
+            acquire_timeout,
+            min_connections,
+            max_lifetime,
+            idle_timeout,
+        } = self;
+
+        f.debug_struct("PostgresArgs")
+            .field("uri", &uri.as_deref().map(mask_url_credentials))
+            .field("host", host)
+            .field("user", user)
+            .field("password", &format_args!("{}", REDACTED))
+            .field("port", port)
+            .field("database", database)
+            .field("schema", schema)
+            .field("ssl_mode", ssl_mode)
+            .field("ssl_root_cert", ssl_root_cert)
+            .field("enable_statement_logging", enable_statement_logging)
+            .field("test_before_acquire", test_before_acquire)
+            .field("max_connections", max_connections)
+            .field("acquire_timeout", acquire_timeout)
+            .field("min_connections", min_connections)
+            .field("max_lifetime", max_lifetime)
+            .field("idle_timeout", idle_timeout)
+            .finish()
+    }
+}
+
 impl PostgresArgs {
+    /// Rejects combinations of values that can't both be honoured.
+    pub fn check(&self) -> Result<(), String> {
+        if self.min_connections > self.max_connections {
+            return Err(format!(
+                "min_connections ({}) must not exceed max_connections ({})",
+                self.min_connections, self.max_connections,
+            ));
+        }
+        Ok(())
+    }
+
     pub async fn connect(&self) -> sqlx::Result<PgPool> {
         self.impl_connect_with_database_and_schema(&self.database, &self.schema)
             .await
@@ -4,7 +4,7 @@
 
 /// Service network configuration
 #[derive(Debug)]
-#[cfg_attr(feature = "env", derive(ground_env::FromEnv))]
+#[cfg_attr(feature = "env", derive(ground_env::FromEnv, ground_env::ToEnv))]
 #[cfg_attr(feature = "clap", derive(clap::Parser))]
 #[cfg_attr(feature = "clap", clap(next_help_heading = "SERVICE", term_width = 200))]
 pub struct ServiceArgs {
@@ -1,12 +1,12 @@
 #[derive(Debug)]
-#[cfg_attr(feature = "env", derive(ground_env::FromEnv))]
+#[cfg_attr(feature = "env", derive(ground_env::FromEnv, ground_env::ToEnv))]
 #[cfg_attr(feature = "clap", derive(clap::Parser))]
 #[cfg_attr(
     feature = "clap",
     clap(next_help_heading = "TRACING", term_width = 200)
 )]
 pub struct TracingArgs {
-    #[cfg_attr(feature = "env", env(rename = "HUMAN_READABLE_LOGS", default))]
+    #[cfg_attr(feature = "env", env(rename = "HUMAN_READABLE_LOGS", parse_with = "ground_env::formats::bool", default))]
     #[cfg_attr(
         feature = "clap",
         clap(name = "human-readable-logs", env = "HUMAN_READABLE_LOGS", long)
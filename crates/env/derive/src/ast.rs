@@ -1,21 +1,55 @@
 use darling::util::Override;
 use proc_macro2::Ident;
 
-pub(crate) type EnvData = darling::ast::Data<(), EnvField>;
+pub(crate) type EnvData = darling::ast::Data<EnvVariant, EnvField>;
 
 #[derive(Debug, darling::FromDeriveInput)]
-#[darling(attributes(env), supports(struct_named))]
+#[darling(attributes(env), supports(struct_named, struct_newtype, struct_tuple, enum_newtype, enum_unit), forward_attrs(doc))]
 pub(crate) struct DeriveInput {
     pub ident: Ident,
+    pub generics: syn::Generics,
+    pub attrs: Vec<syn::Attribute>,
     pub data: EnvData,
     pub root: Option<syn::Path>,
+
+    /// the environment variable that selects the variant of an enum
+    pub tag: Option<syn::LitStr>,
+
+    /// checks the value once every field has been resolved
+    pub validate_with: Option<syn::Path>,
+
+    /// read the only field as the type itself, reading the same variables
+    #[darling(default)]
+    pub transparent: bool,
+
+    /// read every variable of the type with this prefix
+    pub prefix: Option<syn::LitStr>,
+
+    /// derive keys from field idents with this case instead of `SCREAMING_SNAKE_CASE`
+    #[darling(default)]
+    pub rename_all: RenameRule,
+}
+
+#[derive(Clone, Debug, darling::FromVariant)]
+#[darling(attributes(env), forward_attrs(doc))]
+pub(crate) struct EnvVariant {
+    pub ident: Ident,
+    pub attrs: Vec<syn::Attribute>,
+    pub fields: darling::ast::Fields<EnvField>,
+
+    /// match the tag against this value instead of the kebab-cased variant ident
+    pub rename: Option<syn::LitStr>,
+
+    /// read the payload of this variant with the given prefix
+    pub prefix: Option<syn::LitStr>,
 }
 
 #[derive(Clone, Debug, darling::FromField)]
-#[darling(attributes(env))]
+#[darling(attributes(env), forward_attrs(doc))]
 pub(crate) struct EnvField {
     pub ident: Option<Ident>,
     pub ty: syn::Type,
+    pub attrs: Vec<syn::Attribute>,
 
     /// use this environment variable name instead of field ident
     pub rename: Option<syn::LitStr>,
@@ -26,5 +60,164 @@ pub(crate) struct EnvField {
     /// parse string literal into value, or Default::default() when no explicit value provided
     pub flatten: Option<Override<syn::LitStr>>,
 
+    /// flatten with the key of the field as the prefix, followed by the separator (`_` by default)
+    pub prefixed: Option<Override<syn::LitStr>>,
+
     pub delimiter: Option<syn::LitStr>,
+
+    /// parse the value with this function instead of `Parse`
+    pub parse_with: Option<syn::Path>,
+
+    /// parse the value as this type, then convert it with `TryFrom`
+    pub try_from: Option<syn::Type>,
+
+    /// format the value with this function instead of `ToEnvValue`, for `ToEnv`
+    pub format_with: Option<syn::Path>,
+
+    /// write the exposed secret, for `ToEnv`
+    #[darling(default)]
+    pub expose_secret: bool,
+
+    /// collect every variable starting with this prefix into a map, keyed by the rest of the key
+    pub prefix_map: Option<syn::LitStr>,
+
+    /// other names to read the value from, in priority order
+    #[darling(multiple)]
+    pub alias: Vec<syn::LitStr>,
+
+    /// old names to read the value from (after the aliases), with a warning
+    #[darling(multiple)]
+    pub deprecated: Vec<syn::LitStr>,
+
+    /// keep the value out of errors, and out of `RedactedDebug` output
+    #[darling(default)]
+    pub secret: bool,
+
+    /// read the file named by `KEY_FILE` when `KEY` is missing
+    #[darling(default)]
+    pub file_fallback: bool,
+
+    /// reject values outside of the (inclusive) bounds
+    pub range: Option<Range>,
+
+    /// reject values that don't match the pattern, which has to match the whole value
+    pub regex: Option<syn::LitStr>,
+
+    /// reject empty values
+    #[darling(default)]
+    pub non_empty: bool,
+
+    /// reject values that aren't one of the listed values
+    pub one_of: Option<syn::ExprArray>,
+
+    /// reject values the function returns an error for
+    pub validate: Option<syn::Path>,
+}
+
+impl EnvField {
+    pub fn has_attributes(&self) -> bool {
+        self.rename.is_some()
+            || self.default.is_some()
+            || self.flatten.is_some()
+            || self.prefixed.is_some()
+            || self.delimiter.is_some()
+            || self.prefix_map.is_some()
+            || self.parse_with.is_some()
+            || self.try_from.is_some()
+            || self.format_with.is_some()
+            || self.expose_secret
+            || self.secret
+            || self.file_fallback
+            || !self.alias.is_empty()
+            || !self.deprecated.is_empty()
+            || self.has_validation()
+    }
+
+    pub fn has_validation(&self) -> bool {
+        self.range.is_some()
+            || self.regex.is_some()
+            || self.non_empty
+            || self.one_of.is_some()
+            || self.validate.is_some()
+    }
+}
+
+/// How keys are derived from field idents, named like serde's `rename_all`.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) enum RenameRule {
+    #[default]
+    ScreamingSnake,
+    Snake,
+    Kebab,
+    ScreamingKebab,
+    Lower,
+    Upper,
+    Camel,
+    Pascal,
+}
+
+impl RenameRule {
+    const NAMES: [(&'static str, RenameRule); 8] = [
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("snake_case", RenameRule::Snake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("camelCase", RenameRule::Camel),
+        ("PascalCase", RenameRule::Pascal),
+    ];
+
+    pub fn apply(self, name: &str) -> String {
+        use heck::{ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+
+        match self {
+            RenameRule::ScreamingSnake => name.to_shouty_snake_case(),
+            RenameRule::Snake => name.to_snake_case(),
+            RenameRule::Kebab => name.to_kebab_case(),
+            RenameRule::ScreamingKebab => name.to_shouty_kebab_case(),
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Camel => name.to_lower_camel_case(),
+            RenameRule::Pascal => name.to_upper_camel_case(),
+        }
+    }
+}
+
+impl darling::FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Self::NAMES.iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let names = Self::NAMES.iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>();
+                darling::Error::custom(format!("unknown rename rule, expected one of: {}", names.join(", ")))
+            })
+    }
+}
+
+#[derive(Clone, Debug, darling::FromMeta)]
+pub(crate) struct Range {
+    pub min: Option<syn::Expr>,
+    pub max: Option<syn::Expr>,
+}
+
+#[derive(Debug, darling::FromDeriveInput)]
+#[darling(attributes(env), supports(enum_unit))]
+pub(crate) struct EnumInput {
+    pub ident: Ident,
+    pub data: darling::ast::Data<EnumVariant, ()>,
+    pub root: Option<syn::Path>,
+}
+
+#[derive(Clone, Debug, darling::FromVariant)]
+#[darling(attributes(env))]
+pub(crate) struct EnumVariant {
+    pub ident: Ident,
+
+    /// additional names this variant can be parsed from
+    #[darling(multiple)]
+    pub alias: Vec<syn::LitStr>,
 }
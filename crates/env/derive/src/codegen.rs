@@ -1,5 +1,5 @@
 use darling::util::Override;
-use heck::ToShoutySnakeCase;
+use heck::{ToKebabCase, ToSnakeCase};
 use proc_macro2::TokenStream;
 
 use crate::{ast, utils};
@@ -7,46 +7,509 @@ use crate::{ast, utils};
 pub fn expand(root: TokenStream, input: ast::DeriveInput) -> TokenStream {
     let ident = input.ident;
     let span = ident.span();
+    let generics = add_bounds(&root, input.generics, &input.data, input.transparent, false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let impl_header = quote::quote_spanned! {span=>
+        impl #impl_generics #root::FromEnv for #ident #ty_generics #where_clause
+    };
+    let ty_name = ident.to_string();
+    let doc = option_tokens(utils::doc_string(&input.attrs));
+
+    // Runs after every field has been resolved, including those of nested types.
+    let validate = match &input.validate_with {
+        Some(validate_with) => quote::quote_spanned! {span=>
+            #root::validate::custom(#validate_with(&__value))
+                .map_err(|reason| ctx.invalid_group(#ty_name, reason))?;
+        },
+        None => quote::quote! {},
+    };
 
     match input.data {
-        ast::EnvData::Enum(_) => {
-            quote::quote_spanned! {span=>
-                compile_error!("enum not supported");
+        ast::EnvData::Enum(variants) => match input.tag {
+            Some(_) if input.transparent => quote::quote_spanned! {span=>
+                compile_error!("#[env(transparent)] can only be used on structs");
+            },
+            Some(tag) => {
+                let (read, description) = expand_enum(&root, &ident, doc, validate, tag, variants);
+                expand_impl(&root, impl_header, input.prefix.as_ref(), read, description)
             }
-        }
+            None => quote::quote_spanned! {span=>
+                compile_error!("enums require #[env(tag = \"...\")] to select the variant");
+            },
+        },
         ast::EnvData::Struct(fields) => {
+            if input.tag.is_some() {
+                return quote::quote_spanned! {span=>
+                    compile_error!("#[env(tag = \"...\")] can only be used on enums");
+                };
+            }
+
+            let tuple = fields.style == darling::ast::Style::Tuple;
+
+            if input.transparent {
+                let field = match <[_; 1]>::try_from(fields.fields) {
+                    Ok([field]) => field,
+                    Err(_) => return quote::quote_spanned! {span=>
+                        compile_error!("#[env(transparent)] requires exactly one field");
+                    },
+                };
+                return match expand_transparent(&root, &ident, doc, validate, field, tuple) {
+                    Ok((read, description)) => expand_impl(&root, impl_header, input.prefix.as_ref(), read, description),
+                    Err(err) => err,
+                };
+            }
+
+            let newtype = tuple && fields.fields.len() == 1;
+            let mut resolve = quote::quote! {};
             let mut init = quote::quote! {};
+            let mut items = quote::quote! {};
 
-            for field in fields {
-                let field_ident = field.ident.clone().unwrap();
+            for (index, field) in fields.into_iter().enumerate() {
+                let field_ty = field.ty.clone();
+                let binding = quote::format_ident!("__field_{}", index);
+
+                // Tuple fields have no name to derive a key from, so every field must be renamed (or flattened).
+                let (field_ident, name) = match &field.ident {
+                    Some(field_ident) => (field_ident.clone(), field_ident.to_string()),
+                    None => {
+                        if field.rename.is_none() && field.flatten.is_none() && field.prefix_map.is_none() {
+                            let span = syn::spanned::Spanned::span(&field.ty);
+                            let message = if newtype {
+                                "fields of tuple structs require #[env(rename = \"...\")], or #[env(transparent)] on the struct to read the field as the struct"
+                            } else {
+                                "fields of tuple structs require #[env(rename = \"...\")]"
+                            };
+                            return quote::quote_spanned! {span=>
+                                compile_error!(#message);
+                            };
+                        }
+                        let span = syn::spanned::Spanned::span(&field.ty);
+                        (proc_macro2::Ident::new(&format!("field_{}", index), span), index.to_string())
+                    }
+                };
 
-                let field_init = expand_field(&root, field, &field_ident);
+                items.extend(describe_field(&root, &field, &field_ident, &name, input.rename_all));
 
-                init.extend(quote::quote_spanned! {span=> #field_ident: { #field_init },})
+                let field_init = expand_field(&root, field, &field_ident, input.rename_all);
+
+                // Every field is resolved, even if an earlier one failed, so all of the errors can be reported at once.
+                resolve.extend(quote::quote_spanned! {span=>
+                    let #binding = __errors.collect((|| -> #root::Result<#field_ty> { Ok(#field_init) })());
+                });
+                if tuple {
+                    init.extend(quote::quote_spanned! {span=> #root::Errors::take(#binding),})
+                } else {
+                    init.extend(quote::quote_spanned! {span=> #field_ident: #root::Errors::take(#binding),})
+                }
             }
 
-            quote::quote_spanned! { span=>
-                impl #root::FromEnv for #ident {
-                    #[allow(unused_variables)]
-                    fn from_ctx(ctx: &mut #root::Context) -> #root::Result<Self> {
-                        Ok(Self {
-                            #init
-                        })
-                    }
+            let init = if tuple {
+                quote::quote_spanned! {span=> Self(#init) }
+            } else {
+                quote::quote_spanned! {span=> Self { #init } }
+            };
+
+            let read = quote::quote_spanned! {span=>
+                let mut __errors = #root::Errors::default();
+                #resolve
+                __errors.finish()?;
+                let __value = #init;
+                #validate
+                Ok(__value)
+            };
+            let description = quote::quote_spanned! {span=>
+                #root::describe::Description {
+                    ty: #ty_name,
+                    doc: #doc,
+                    items: vec![#items],
+                }
+            };
+
+            expand_impl(&root, impl_header, input.prefix.as_ref(), read, description)
+        }
+    }
+}
+
+/// Reads the only field as the type itself, such that the wrapper reads the same variables as the field.
+///
+/// Returns the body of `from_ctx` and the expression returned by `describe`.
+fn expand_transparent(
+    root: &TokenStream,
+    ident: &proc_macro2::Ident,
+    doc: TokenStream,
+    validate: TokenStream,
+    field: ast::EnvField,
+    tuple: bool,
+) -> Result<(TokenStream, TokenStream), TokenStream> {
+    let span = ident.span();
+    let ty_name = ident.to_string();
+    let ty = &field.ty;
+
+    if field.has_attributes() {
+        let span = syn::spanned::Spanned::span(ty);
+        return Err(quote::quote_spanned! {span=>
+            compile_error!("field attributes cannot be used with #[env(transparent)]");
+        });
+    }
+
+    let init = match &field.ident {
+        Some(field_ident) if !tuple => quote::quote_spanned! {span=> Self { #field_ident: __inner } },
+        _ => quote::quote_spanned! {span=> Self(__inner) },
+    };
+
+    let read = quote::quote_spanned! {span=>
+        let __inner = <#ty as #root::FromEnv>::from_ctx(ctx)?;
+        let __value = #init;
+        #validate
+        Ok(__value)
+    };
+    let description = quote::quote_spanned! {span=> {
+        let inner = <#ty as #root::FromEnv>::describe();
+        #root::describe::Description {
+            ty: #ty_name,
+            doc: #doc.or(inner.doc),
+            items: inner.items,
+        }
+    }};
+
+    Ok((read, description))
+}
+
+/// Implements `FromEnv` with the body of `from_ctx` and the expression returned by `describe`, applying the prefix of the type.
+fn expand_impl(
+    root: &TokenStream,
+    impl_header: TokenStream,
+    prefix: Option<&syn::LitStr>,
+    read: TokenStream,
+    description: TokenStream,
+) -> TokenStream {
+    let read = scoped(prefix, read);
+    let description = describe_scoped(prefix, description);
+
+    quote::quote! {
+        #impl_header {
+            #[allow(unused_variables, clippy::redundant_closure_call, clippy::needless_question_mark)]
+            fn from_ctx(ctx: &mut #root::Context) -> #root::Result<Self> {
+                #read
+            }
+
+            fn describe() -> #root::describe::Description {
+                #description
+            }
+        }
+    }
+}
+
+/// Bounds every field type that mentions a type parameter by the trait used to read it (or write it, for `ToEnv`).
+pub(crate) fn add_bounds(
+    root: &TokenStream,
+    mut generics: syn::Generics,
+    data: &ast::EnvData,
+    transparent: bool,
+    to_env: bool,
+) -> syn::Generics {
+    let (group_trait, value_trait) = if to_env {
+        (quote::quote!(#root::ToEnv), quote::quote!(#root::ToEnvValue))
+    } else {
+        (quote::quote!(#root::FromEnv), quote::quote!(#root::Parse))
+    };
+
+    let params = generics.type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    // Enum payloads and transparent fields are read as a whole.
+    let (fields, whole): (Vec<&ast::EnvField>, bool) = match data {
+        ast::EnvData::Enum(variants) => (
+            variants.iter()
+                .flat_map(|variant| variant.fields.fields.iter())
+                .collect(),
+            true,
+        ),
+        ast::EnvData::Struct(fields) => (fields.fields.iter().collect(), transparent),
+    };
+
+    let mut predicates: Vec<syn::WherePredicate> = vec![];
+    for field in fields {
+        let ty = &field.ty;
+        if !utils::uses_type_param(ty, &params) {
+            continue;
+        }
+
+        if whole || field.flatten.is_some() {
+            // Lists are read element by element, and optional groups as the inner type.
+            let ty = match field.flatten {
+                Some(_) => utils::subty_if_name(ty, "Vec")
+                    .or_else(|| utils::subty_if_name(ty, "Option"))
+                    .unwrap_or(ty),
+                None => ty,
+            };
+            predicates.push(syn::parse_quote!(#ty: #group_trait));
+            continue;
+        }
+
+        // Custom parsers (and formatters) bring their own requirements.
+        let custom = if to_env {
+            field.format_with.is_some() || field.expose_secret
+        } else {
+            field.parse_with.is_some() || field.try_from.is_some()
+        };
+        if custom {
+            continue;
+        }
+
+        let inner_ty = utils::subty_if_name(ty, "Option")
+            .or_else(|| utils::subty_if_name(ty, "Vec"))
+            .or_else(|| field.prefix_map.as_ref().and_then(|_| utils::map_value_ty(ty)));
+        match inner_ty {
+            Some(inner_ty) => predicates.push(syn::parse_quote!(#inner_ty: #value_trait)),
+            None => {
+                predicates.push(syn::parse_quote!(#ty: #value_trait));
+                if let (Some(Override::Inherit), false) = (&field.default, to_env) {
+                    predicates.push(syn::parse_quote!(#ty: ::std::default::Default));
                 }
             }
         }
     }
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Returns the body of `from_ctx` and the expression returned by `describe`.
+fn expand_enum(
+    root: &TokenStream,
+    ident: &proc_macro2::Ident,
+    doc: TokenStream,
+    validate: TokenStream,
+    tag: syn::LitStr,
+    variants: Vec<ast::EnvVariant>,
+) -> (TokenStream, TokenStream) {
+    let span = ident.span();
+    let ty_name = ident.to_string();
+
+    let mut tags = vec![];
+    let mut arms = quote::quote! {};
+    let mut items = quote::quote! {};
+
+    for (index, variant) in variants.into_iter().enumerate() {
+        let variant_ident = &variant.ident;
+        let span = variant_ident.span();
+
+        let value = match variant.rename {
+            Some(rename) => rename.value(),
+            None => variant_ident.to_string().to_kebab_case(),
+        };
+
+        let (init, group) = match variant.fields.fields.first() {
+            None => (
+                quote::quote_spanned! {span=>
+                    Self::#variant_ident
+                },
+                quote::quote_spanned! {span=>
+                    None
+                },
+            ),
+            Some(field) => {
+                let ty = &field.ty;
+                let prefix = variant.prefix.as_ref().map(syn::LitStr::value).unwrap_or_default();
+                let payload = match &variant.prefix {
+                    Some(prefix) => quote::quote_spanned! {span=>
+                        ctx.with_prefix::<#ty>(#prefix)?
+                    },
+                    None => quote::quote_spanned! {span=>
+                        <#ty as #root::FromEnv>::from_ctx(ctx)?
+                    },
+                };
+                (
+                    quote::quote_spanned! {span=>
+                        Self::#variant_ident(#payload)
+                    },
+                    quote::quote_spanned! {span=>
+                        Some(#root::describe::Group {
+                            prefix: String::from(#prefix),
+                            optional: false,
+                            description: <#ty as #root::FromEnv>::describe().with_prefix(#prefix),
+                        })
+                    },
+                )
+            }
+        };
+
+        arms.extend(quote::quote_spanned! {span=>
+            #index => #init,
+        });
+
+        let variant_name = variant_ident.to_string();
+        let variant_doc = option_tokens(utils::doc_string(&variant.attrs));
+        items.extend(quote::quote_spanned! {span=>
+            #root::describe::Item {
+                ident: #variant_name,
+                doc: #variant_doc,
+                kind: #root::describe::ItemKind::Variant(#root::describe::Variant {
+                    key: String::from(#tag),
+                    value: #value,
+                    group: #group,
+                }),
+            },
+        });
+
+        tags.push(value);
+    }
+
+    let read = quote::quote_spanned! {span=>
+        let __value = match ctx.resolve_tag(#tag, &[#(#tags),*])? {
+            #arms
+            _ => unreachable!("resolve_tag only returns indices of the given tags"),
+        };
+        #validate
+        Ok(__value)
+    };
+
+    let description = quote::quote_spanned! {span=>
+        #root::describe::Description {
+            ty: #ty_name,
+            doc: #doc,
+            items: vec![
+                // The tag itself.
+                #root::describe::Item {
+                    ident: #ty_name,
+                    doc: None,
+                    kind: #root::describe::ItemKind::Var(#root::describe::Var {
+                        key: String::from(#tag),
+                        ty: #ty_name,
+                        default: None,
+                        optional: false,
+                        delimiter: None,
+                        file_fallback: false,
+                        parse_with: false,
+                        aliases: vec![],
+                        deprecated: vec![],
+                    }),
+                },
+                #items
+            ],
+        }
+    };
+
+    (read, description)
+}
+
+pub fn expand_enum_parse(root: TokenStream, input: ast::EnumInput) -> TokenStream {
+    let ident = input.ident;
+    let span = ident.span();
+
+    let variants = match input.data {
+        darling::ast::Data::Enum(variants) => variants,
+        darling::ast::Data::Struct(_) => unreachable!("darling only accepts unit enums"),
+    };
+
+    let mut choices = quote::quote! {};
+    let mut arms = quote::quote! {};
+    let mut values = quote::quote! {};
+
+    for (index, variant) in variants.into_iter().enumerate() {
+        let variant_ident = &variant.ident;
+        let span = variant_ident.span();
+        let name = variant_ident.to_string();
+
+        // Names are compared case-insensitively, so the shouty snake case is covered by the snake case.
+        let mut names = vec![name.to_kebab_case(), name.to_snake_case(), name.clone()];
+        names.extend(variant.alias.iter().map(syn::LitStr::value));
+        let mut seen = std::collections::HashSet::new();
+        names.retain(|name| seen.insert(name.to_ascii_lowercase()));
+
+        // The first name is also what's written, as it's listed in errors.
+        let value = &names[0];
+        values.extend(quote::quote_spanned! {span=> Self::#variant_ident => #value, });
+
+        choices.extend(quote::quote_spanned! {span=> &[#(#names),*], });
+        arms.extend(quote::quote_spanned! {span=> #index => Self::#variant_ident, });
+    }
+
+    quote::quote_spanned! { span=>
+        impl #root::Parse for #ident {
+            fn parse(input: &str) -> #root::Result<Self> {
+                Ok(match #root::parse_variant::<Self>(input, &[#choices])? {
+                    #arms
+                    _ => unreachable!("parse_variant only returns indices of the given choices"),
+                })
+            }
+        }
+
+        impl #root::ToEnvValue for #ident {
+            fn to_env_value(&self) -> String {
+                String::from(match self {
+                    #values
+                })
+            }
+        }
+    }
 }
 
 fn expand_field(
     root: &TokenStream,
     field: ast::EnvField,
     field_ident: &proc_macro2::Ident,
+    rename_all: ast::RenameRule,
 ) -> TokenStream {
     let span = field_ident.span();
 
-    if let Some(flatten) = field.flatten {
+    if let Some(prefixed) = &field.prefixed {
+        let message = match (&field.flatten, &field.ident) {
+            (None, _) => Some("#[env(prefixed)] requires `flatten`"),
+            (Some(Override::Explicit(_)), _) => Some("#[env(prefixed)] cannot be used with a prefix given to `flatten`"),
+            (_, None) => Some("#[env(prefixed)] requires a named field"),
+            _ => None,
+        };
+        if let Some(message) = message {
+            let span = match prefixed {
+                Override::Explicit(separator) => separator.span(),
+                Override::Inherit => span,
+            };
+            return quote::quote_spanned! { span=>
+                compile_error!(#message)
+            };
+        }
+    }
+
+    if let Some(prefix) = &field.prefix_map {
+        if field.flatten.is_some() || field.rename.is_some() || field.default.is_some() || field.delimiter.is_some() {
+            return quote::quote_spanned! { span=>
+                compile_error!("#[env(prefix_map = "...")] cannot be used with `flatten`, `rename`, `default` or `delimiter`")
+            };
+        }
+        if field.file_fallback || field.has_validation() {
+            return quote::quote_spanned! { span=>
+                compile_error!("#[env(prefix_map = "...")] cannot be used with `file_fallback` or validation attributes")
+            };
+        }
+        if field.parse_with.is_some() || field.try_from.is_some() {
+            return quote::quote_spanned! { span=>
+                compile_error!("`parse_with` and `try_from` cannot be used with `prefix_map`")
+            };
+        }
+        if !field.alias.is_empty() || !field.deprecated.is_empty() {
+            return quote::quote_spanned! { span=>
+                compile_error!("`alias` and `deprecated` cannot be used with `prefix_map`")
+            };
+        }
+        if field.secret {
+            return quote::quote_spanned! { span=>
+                compile_error!("#[env(secret)] cannot be used with `prefix_map`")
+            };
+        }
+        return match utils::map_value_ty(&field.ty) {
+            Some(value_ty) => quote::quote_spanned! { span=>
+                ctx.resolve_map::<#value_ty, _>(#prefix)?
+            },
+            None => quote::quote_spanned! { span=>
+                compile_error!("#[env(prefix_map = "...")] requires a `HashMap<String, _>` or `BTreeMap<String, _>`")
+            },
+        };
+    }
+
+    if let Some(flatten) = &field.flatten {
         if field.rename.is_some() {
             return quote::quote_spanned! { span=>
                 compile_error!("#[env(rename = "...")] cannot be used with `flatten`")
@@ -62,31 +525,205 @@ fn expand_field(
                 compile_error!("#[env(default = "...")] cannot be used with `flatten`")
             };
         }
-        return match flatten {
-            Override::Inherit => {
-                quote::quote_spanned! { span=>
-                    <_ as #root::FromEnv>::from_ctx(ctx)?
-                }
-            }
-            Override::Explicit(prefix) => {
-                quote::quote_spanned! { span=>
-                    ctx.with_prefix::<_>(#prefix)?
-                }
+        if field.file_fallback {
+            return quote::quote_spanned! { span=>
+                compile_error!("#[env(file_fallback)] cannot be used with `flatten`")
+            };
+        }
+        if !field.alias.is_empty() || !field.deprecated.is_empty() {
+            return quote::quote_spanned! { span=>
+                compile_error!("`alias` and `deprecated` cannot be used with `flatten`")
+            };
+        }
+        if field.has_validation() {
+            return quote::quote_spanned! { span=>
+                compile_error!("validation attributes cannot be used with `flatten`")
+            };
+        }
+        if field.parse_with.is_some() || field.try_from.is_some() {
+            return quote::quote_spanned! { span=>
+                compile_error!("`parse_with` and `try_from` cannot be used with `flatten`")
+            };
+        }
+        if field.secret {
+            return quote::quote_spanned! { span=>
+                compile_error!("#[env(secret)] cannot be used with `flatten`, mark the fields of the group instead")
+            };
+        }
+        let prefix = flatten_prefix(flatten, &field, field_ident, rename_all);
+        if let Some(element_ty) = utils::subty_if_name(&field.ty, "Vec") {
+            // The elements would otherwise be read from `0_...`, `1_...`, ...
+            if prefix.is_empty() {
+                return quote::quote_spanned! { span=>
+                    compile_error!("a flattened `Vec` requires a prefix, use #[env(flatten = \"...\")] or #[env(flatten, prefixed)]")
+                };
             }
+            return quote::quote_spanned! { span=>
+                ctx.resolve_list::<#element_ty>(#prefix)?
+            };
+        }
+        if let Some(inner_ty) = utils::subty_if_name(&field.ty, "Option") {
+            return quote::quote_spanned! { span=>
+                ctx.resolve_optional::<#inner_ty>(#prefix)?
+            };
+        }
+        return match prefix.is_empty() {
+            true => quote::quote_spanned! { span=>
+                <_ as #root::FromEnv>::from_ctx(ctx)?
+            },
+            false => quote::quote_spanned! { span=>
+                ctx.with_prefix::<_>(#prefix)?
+            },
         };
     }
 
-    let value = {
-        let key = match field.rename {
-            Some(rename) => rename.value(),
-            None => field_ident.to_string().to_shouty_snake_case(),
+    // Patterns match the whole value, as `validate::regex` anchors them.
+    #[cfg(feature = "regex")]
+    if let Some(pattern) = &field.regex {
+        if let Err(err) = regex::Regex::new(&format!("^(?:{})$", pattern.value())) {
+            let message = format!("invalid `regex` pattern: {}", err);
+            return quote::quote_spanned! { pattern.span()=>
+                compile_error!(#message)
+            };
+        }
+    }
+    #[cfg(not(feature = "regex"))]
+    if let Some(pattern) = &field.regex {
+        return quote::quote_spanned! { pattern.span()=>
+            compile_error!("#[env(regex)] requires the `regex` feature of ground-env")
         };
+    }
 
-        quote::quote_spanned! {span=>
-            ctx.resolve(#key)
+    let key = field_key(&field, field_ident, rename_all);
+
+    match expand_validation(root, &field, span) {
+        Some(check) if field.secret => {
+            let ty = field.ty.clone();
+            let secrets = syn::Ident::new("__secrets", span);
+            let value = expand_value(root, field, &key, span, Some(&secrets));
+            quote::quote_spanned! { span=> {
+                let #secrets = #root::redact::Secrets::default();
+                let value: #ty = #value;
+                #check.map_err(|reason| ctx.invalid(#key, #secrets.redact(reason)))?;
+                value
+            }}
+        }
+        Some(check) => {
+            let ty = field.ty.clone();
+            let value = expand_value(root, field, &key, span, None);
+            quote::quote_spanned! { span=> {
+                let value: #ty = #value;
+                #check.map_err(|reason| ctx.invalid(#key, reason))?;
+                value
+            }}
         }
+        None => expand_value(root, field, &key, span, None),
+    }
+}
+
+fn expand_value(
+    root: &TokenStream,
+    field: ast::EnvField,
+    key: &str,
+    span: proc_macro2::Span,
+    secrets: Option<&syn::Ident>,
+) -> TokenStream {
+    // `PathBuf` and `OsString` are read as raw OS values, as they don't have to be UTF-8.
+    let os = field.parse_with.is_none()
+        && field.try_from.is_none()
+        && utils::is_os_ty(utils::subty_if_name(&field.ty, "Option").unwrap_or(&field.ty));
+
+    let value = {
+        let file_fallback = field.file_fallback;
+
+        match (field.alias.is_empty() && field.deprecated.is_empty(), os) {
+            (true, false) => quote::quote_spanned! {span=>
+                ctx.resolve(#key, #file_fallback)
+            },
+            (true, true) => quote::quote_spanned! {span=>
+                ctx.resolve_os(#key, #file_fallback)
+            },
+            (false, _) => {
+                let aliases = &field.alias;
+                let deprecated = &field.deprecated;
+                let resolve = match os {
+                    true => quote::quote_spanned! {span=> resolve_aliased_os },
+                    false => quote::quote_spanned! {span=> resolve_aliased },
+                };
+                quote::quote_spanned! {span=>
+                    ctx.#resolve(#key, &[#(#aliases),*], &[#(#deprecated),*], #file_fallback)
+                }
+            }
+        }
+    };
+
+    let value = match field.secret {
+        true => quote::quote_spanned! {span=>
+            #root::redact::redact_err(#value)
+        },
+        false => value,
+    };
+
+    // Records where the value came from when it falls back to the default.
+    let value = match &field.default {
+        Some(default) => {
+            let origin = match default {
+                Override::Explicit(default) => quote::quote_spanned! {span=>
+                    #root::Origin::Default(#default)
+                },
+                Override::Inherit => quote::quote_spanned! {span=>
+                    #root::Origin::InheritedDefault
+                },
+            };
+            quote::quote_spanned! {span=>
+                {
+                    let value = #value;
+                    ctx.defaulted(value, #origin)
+                }
+            }
+        }
+        None => value,
+    };
+
+    // Parses a `&str` (or a `&OsStr`) into the field type (or the type inside the `Option` or `Vec`).
+    let parse = match (&field.parse_with, &field.try_from) {
+        (Some(_), Some(_)) => {
+            return quote::quote_spanned! { span=>
+                compile_error!("#[env(parse_with = "...")] cannot be used with `try_from`")
+            };
+        }
+        (Some(parse_with), None) => quote::quote_spanned! {span=>
+            |value: &str| #root::parse_with(value, #parse_with)
+        },
+        (None, Some(raw_ty)) => quote::quote_spanned! {span=>
+            |value: &str| #root::parse_try_from::<#raw_ty, _>(value)
+        },
+        (None, None) if os => quote::quote_spanned! {span=>
+            #root::ParseOs::parse_os
+        },
+        (None, None) => quote::quote_spanned! {span=>
+            #root::Parse::parse
+        },
+    };
+    let record = secrets.map(|secrets| quote::quote_spanned! {span=> #secrets.record(value); });
+    let parse = match (field.secret, os) {
+        (true, false) => quote::quote_spanned! {span=>
+            |value: &str| { #record #root::redact::redact_err((#parse)(value)) }
+        },
+        (true, true) => quote::quote_spanned! {span=>
+            |value: &::std::ffi::OsStr| { #record #root::redact::redact_err((#parse)(value)) }
+        },
+        (false, _) => parse,
     };
 
+    let default = field.default.map(|default| match default {
+        Override::Explicit(default) if os => Override::Explicit(quote::quote_spanned! {span=>
+            ::std::ffi::OsStr::new(#default)
+        }),
+        Override::Explicit(default) => Override::Explicit(quote::quote!(#default)),
+        Override::Inherit => Override::Inherit,
+    });
+
     match utils::subty_if_name(&field.ty, "Option") {
         Some(sub_ty) => {
             if utils::is_generic_ty(sub_ty, "Option") {
@@ -98,7 +735,7 @@ fn expand_field(
                     compile_error!("Option<Vec<_>> is not supported")
                 }
             } else {
-                expand_optional_field(root, span, value, field.default)
+                expand_optional_field(root, span, key, value, parse, default)
             }
         }
         None => match utils::subty_if_name(&field.ty, "Vec") {
@@ -115,33 +752,89 @@ fn expand_field(
                     expand_vec_field(
                         root,
                         span,
+                        key,
                         value,
-                        field.default,
+                        parse,
+                        default,
                         field.delimiter,
                     )
                 }
             }
-            None => expand_mandatory_field(root, span, value, field.default),
+            None => expand_mandatory_field(root, span, key, value, parse, default),
         },
     }
 }
 
+/// Checks the parsed `value`, the value inside an `Option`, or every element of a `Vec`, returning the reason it's invalid.
+fn expand_validation(
+    root: &TokenStream,
+    field: &ast::EnvField,
+    span: proc_macro2::Span,
+) -> Option<TokenStream> {
+    if !field.has_validation() {
+        return None;
+    }
+
+    let mut checks = quote::quote! {};
+
+    if let Some(range) = &field.range {
+        if let Some(min) = &range.min {
+            checks.extend(quote::quote_spanned! {span=> #root::validate::min(value, &#min)?; });
+        }
+        if let Some(max) = &range.max {
+            checks.extend(quote::quote_spanned! {span=> #root::validate::max(value, &#max)?; });
+        }
+    }
+    if field.non_empty {
+        checks.extend(quote::quote_spanned! {span=> #root::validate::non_empty(value)?; });
+    }
+    if let Some(regex) = &field.regex {
+        checks.extend(quote::quote_spanned! {span=> {
+            static REGEX: ::std::sync::OnceLock<#root::validate::Regex> = ::std::sync::OnceLock::new();
+            #root::validate::regex(value, &REGEX, #regex)?;
+        }});
+    }
+    if let Some(one_of) = &field.one_of {
+        checks.extend(quote::quote_spanned! {span=> #root::validate::one_of(value, &#one_of)?; });
+    }
+    if let Some(validate) = &field.validate {
+        checks.extend(quote::quote_spanned! {span=> #root::validate::custom(#validate(value))?; });
+    }
+
+    let (inner_ty, apply) = match utils::subty_if_name(&field.ty, "Option")
+        .or_else(|| utils::subty_if_name(&field.ty, "Vec"))
+    {
+        Some(inner_ty) => (inner_ty, quote::quote_spanned! {span=> value.iter().try_for_each(check) }),
+        None => (&field.ty, quote::quote_spanned! {span=> check(&value) }),
+    };
+
+    Some(quote::quote_spanned! {span=> {
+        let check = |value: &#inner_ty| -> std::result::Result<(), String> {
+            #checks
+            Ok(())
+        };
+        #apply
+    }})
+}
+
 fn expand_optional_field(
     root: &TokenStream,
     span: proc_macro2::Span,
+    key: &str,
     value: TokenStream,
-    default: Option<Override<syn::LitStr>>,
+    parse: TokenStream,
+    default: Option<Override<TokenStream>>,
 ) -> TokenStream {
     match default {
         Some(Override::Explicit(default)) => quote::quote_spanned! { span=>
-            Some(#root::flatten_err(#root::transpose_err(#value?.map(#root::Parse::parse))?
-                .map_err(|_| #root::Parse::parse(#default)))?)
+            Some(#root::flatten_err(#root::transpose_err(#value?.map(|value| ctx.parsed(#key, (#parse)(&value))))?
+                .map_err(|_| ctx.parsed(#key, (#parse)(#default))))?)
         },
         Some(Override::Inherit) => quote::quote_spanned! { span=>
-            #root::transpose_err(#value?.map(#root::Parse::parse))?.ok()
+            #root::transpose_err(#value?.map(|value| ctx.parsed(#key, (#parse)(&value))))?.ok()
         },
         None => quote::quote_spanned! { span=>
-            #value?.ok().map(#root::Parse::parse).transpose()?
+            #value?.ok().map(|value| ctx.parsed(#key, (#parse)(&value))).transpose()?
         },
     }
 }
@@ -149,18 +842,21 @@ fn expand_optional_field(
 fn expand_mandatory_field(
     root: &TokenStream,
     span: proc_macro2::Span,
+    key: &str,
     value: TokenStream,
-    default: Option<Override<syn::LitStr>>,
+    parse: TokenStream,
+    default: Option<Override<TokenStream>>,
 ) -> TokenStream {
     match default {
-        Some(Override::Explicit(default)) => quote::quote_spanned! { span=>
-            #root::Parse::parse(#value?.unwrap_or(#default))?
-        },
+        Some(Override::Explicit(default)) => quote::quote_spanned! { span=> {
+            let value = #value?;
+            ctx.parsed(#key, (#parse)(value.as_deref().unwrap_or(#default)))?
+        }},
         Some(Override::Inherit) => quote::quote_spanned! { span=>
-            #root::transpose_err(#value?.map(#root::Parse::parse))?.unwrap_or_default()
+            #root::transpose_err(#value?.map(|value| ctx.parsed(#key, (#parse)(&value))))?.unwrap_or_default()
         },
         None => quote::quote_spanned! { span=>
-            #value?.map(#root::Parse::parse).map_err(#root::Error::Missing)??
+            #value?.map(|value| ctx.parsed(#key, (#parse)(&value))).map_err(#root::Error::Missing)??
         },
     }
 }
@@ -168,8 +864,10 @@ fn expand_mandatory_field(
 fn expand_vec_field(
     root: &TokenStream,
     span: proc_macro2::Span,
+    key: &str,
     value: TokenStream,
-    default: Option<Override<syn::LitStr>>,
+    parse: TokenStream,
+    default: Option<Override<TokenStream>>,
     delimiter: Option<syn::LitStr>,
 ) -> TokenStream {
     let delimiter = delimiter
@@ -179,12 +877,17 @@ fn expand_vec_field(
     // This returns a `Result<T, String>`, where `Err` means it was missing.
     let value = quote::quote_spanned! {span=> {
         let result = #value?.map(|value| {
+            // An empty value is an empty list, rather than a list with one empty element.
             #[allow(clippy::single_char_pattern)]
-            value.split(#delimiter)
-                .map(#root::Parse::parse)
-                .collect::<std::result::Result<std::vec::Vec<_>, _>>()
+            let values = match value.is_empty() {
+                true => Ok(std::vec::Vec::new()),
+                false => value.split(#delimiter)
+                    .map(#parse)
+                    .collect::<std::result::Result<std::vec::Vec<_>, _>>(),
+            };
+            ctx.parsed(#key, values)
         });
-        transpose_err(result)?
+        #root::transpose_err(result)?
     }};
 
     match default {
@@ -199,3 +902,164 @@ fn expand_vec_field(
         },
     }
 }
+
+pub(crate) fn field_key(field: &ast::EnvField, field_ident: &proc_macro2::Ident, rename_all: ast::RenameRule) -> String {
+    match &field.rename {
+        Some(rename) => rename.value(),
+        None => rename_all.apply(&field_ident.to_string()),
+    }
+}
+
+/// The prefix of a flattened field, either given, or derived from the key of the field for `prefixed`.
+pub(crate) fn flatten_prefix(
+    flatten: &Override<syn::LitStr>,
+    field: &ast::EnvField,
+    field_ident: &proc_macro2::Ident,
+    rename_all: ast::RenameRule,
+) -> String {
+    match (flatten, &field.prefixed) {
+        (Override::Explicit(prefix), _) => prefix.value(),
+        (Override::Inherit, Some(separator)) => {
+            let separator = match separator {
+                Override::Inherit => String::from("_"),
+                Override::Explicit(separator) => separator.value(),
+            };
+            format!("{}{}", field_key(field, field_ident, rename_all), separator)
+        }
+        (Override::Inherit, None) => String::new(),
+    }
+}
+
+/// Runs `body`, which reads the type, with the prefix of the type, for `#[env(prefix = "...")]`.
+fn scoped(prefix: Option<&syn::LitStr>, body: TokenStream) -> TokenStream {
+    match prefix {
+        Some(prefix) => quote::quote! {
+            ctx.scoped(#prefix, |ctx| {
+                #body
+            })
+        },
+        None => body,
+    }
+}
+
+/// Prefixes every key of the `description`, for `#[env(prefix = "...")]`.
+fn describe_scoped(prefix: Option<&syn::LitStr>, description: TokenStream) -> TokenStream {
+    match prefix {
+        Some(prefix) => quote::quote! {
+            (#description).with_prefix(#prefix)
+        },
+        None => description,
+    }
+}
+
+/// Describes the field as a `describe::Item`, mirroring how `expand_field` resolves it.
+fn describe_field(
+    root: &TokenStream,
+    field: &ast::EnvField,
+    field_ident: &proc_macro2::Ident,
+    name: &str,
+    rename_all: ast::RenameRule,
+) -> TokenStream {
+    let span = field_ident.span();
+    let ty = &field.ty;
+    let doc = option_tokens(utils::doc_string(&field.attrs));
+
+    let kind = match &field.flatten {
+        None if field.prefix_map.is_some() => {
+            let prefix = field.prefix_map.as_ref().map(syn::LitStr::value).unwrap_or_default();
+            let ty_name = utils::map_value_ty(ty)
+                .map(utils::type_string)
+                .unwrap_or_default();
+            quote::quote_spanned! {span=>
+                #root::describe::ItemKind::Map(#root::describe::Map {
+                    prefix: String::from(#prefix),
+                    ty: #ty_name,
+                })
+            }
+        }
+        Some(flatten) => {
+            let prefix = flatten_prefix(flatten, field, field_ident, rename_all);
+            if let Some(element_ty) = utils::subty_if_name(ty, "Vec") {
+                let prefix = format!("{}<N>_", prefix);
+                quote::quote_spanned! {span=>
+                    #root::describe::ItemKind::List(#root::describe::Group {
+                        prefix: String::from(#prefix),
+                        optional: false,
+                        description: <#element_ty as #root::FromEnv>::describe().with_prefix(#prefix),
+                    })
+                }
+            } else {
+                let (ty, optional) = match utils::subty_if_name(ty, "Option") {
+                    Some(inner_ty) => (inner_ty, true),
+                    None => (ty, false),
+                };
+                quote::quote_spanned! {span=>
+                    #root::describe::ItemKind::Group(#root::describe::Group {
+                        prefix: String::from(#prefix),
+                        optional: #optional,
+                        description: <#ty as #root::FromEnv>::describe().with_prefix(#prefix),
+                    })
+                }
+            }
+        }
+        None => {
+            let key = field_key(field, field_ident, rename_all);
+            let ty_name = utils::type_string(ty);
+            let default = match &field.default {
+                Some(Override::Explicit(default)) => quote::quote_spanned! {span=>
+                    Some(#root::describe::DefaultValue::Literal(#default))
+                },
+                Some(Override::Inherit) => quote::quote_spanned! {span=>
+                    Some(#root::describe::DefaultValue::Inherit)
+                },
+                None => quote::quote_spanned! {span=>
+                    None
+                },
+            };
+            let optional = utils::is_generic_ty(ty, "Option");
+            let delimiter = if utils::is_generic_ty(ty, "Vec") {
+                let delimiter = field
+                    .delimiter
+                    .as_ref()
+                    .map(syn::LitStr::value)
+                    .unwrap_or(String::from(","));
+                quote::quote_spanned! {span=> Some(#delimiter) }
+            } else {
+                quote::quote_spanned! {span=> None }
+            };
+            let file_fallback = field.file_fallback;
+            let parse_with = field.parse_with.is_some();
+            let aliases = &field.alias;
+            let deprecated = &field.deprecated;
+
+            quote::quote_spanned! {span=>
+                #root::describe::ItemKind::Var(#root::describe::Var {
+                    key: String::from(#key),
+                    ty: #ty_name,
+                    default: #default,
+                    optional: #optional,
+                    delimiter: #delimiter,
+                    file_fallback: #file_fallback,
+                    parse_with: #parse_with,
+                    aliases: vec![#(String::from(#aliases)),*],
+                    deprecated: vec![#(String::from(#deprecated)),*],
+                })
+            }
+        }
+    };
+
+    quote::quote_spanned! {span=>
+        #root::describe::Item {
+            ident: #name,
+            doc: #doc,
+            kind: #kind,
+        },
+    }
+}
+
+fn option_tokens(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote::quote! { Some(#value) },
+        None => quote::quote! { None },
+    }
+}
@@ -1,6 +1,8 @@
 mod ast;
 mod utils;
 mod codegen;
+mod redact;
+mod to_env;
 
 #[proc_macro_derive(FromEnv, attributes(env))]
 pub fn env_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -12,13 +14,60 @@ pub fn env_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         }
     };
 
-    let root = input
-        .root
-        .as_ref()
+    let root = root_path(input.root.as_ref());
+
+    codegen::expand(root, input).into()
+}
+
+#[proc_macro_derive(ToEnv, attributes(env))]
+pub fn to_env_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    let input: ast::DeriveInput = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    let root = root_path(input.root.as_ref());
+
+    to_env::expand(root, input).into()
+}
+
+#[proc_macro_derive(RedactedDebug, attributes(env))]
+pub fn redacted_debug_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    let input: ast::DeriveInput = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    let root = root_path(input.root.as_ref());
+
+    redact::expand(root, input).into()
+}
+
+#[proc_macro_derive(EnvEnum, attributes(env))]
+pub fn env_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    let input: ast::EnumInput = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    let root = root_path(input.root.as_ref());
+
+    codegen::expand_enum_parse(root, input).into()
+}
+
+fn root_path(root: Option<&syn::Path>) -> proc_macro2::TokenStream {
+    root
         .map(|path| quote::quote!(#path))
         .unwrap_or(quote::quote! {
             ::ground_env
-        });
-
-    codegen::expand(root, input).into()
+        })
 }
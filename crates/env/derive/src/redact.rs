@@ -0,0 +1,111 @@
+use proc_macro2::TokenStream;
+
+use crate::{ast, utils};
+
+pub fn expand(root: TokenStream, input: ast::DeriveInput) -> TokenStream {
+    let ident = input.ident;
+    let span = ident.span();
+    let name = ident.to_string();
+    let generics = add_bounds(input.generics, &input.data);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match input.data {
+        ast::EnvData::Enum(variants) => {
+            let mut arms = quote::quote! {};
+            for variant in variants {
+                let variant_ident = &variant.ident;
+                let variant_name = variant_ident.to_string();
+                arms.extend(match variant.fields.fields.first() {
+                    None => quote::quote_spanned! {span=>
+                        Self::#variant_ident => f.write_str(#variant_name),
+                    },
+                    Some(field) => {
+                        let value = format_field(&root, field, quote::quote!(payload));
+                        quote::quote_spanned! {span=>
+                            Self::#variant_ident(payload) => f.debug_tuple(#variant_name).field(#value).finish(),
+                        }
+                    }
+                });
+            }
+            quote::quote! {
+                match self {
+                    #arms
+                }
+            }
+        }
+        ast::EnvData::Struct(fields) => {
+            let tuple = fields.style != darling::ast::Style::Struct;
+            let mut body = match tuple {
+                true => quote::quote_spanned! {span=> f.debug_tuple(#name) },
+                false => quote::quote_spanned! {span=> f.debug_struct(#name) },
+            };
+
+            for (index, field) in fields.iter().enumerate() {
+                match &field.ident {
+                    Some(field_ident) if !tuple => {
+                        let field_name = field_ident.to_string();
+                        let value = format_field(&root, field, quote::quote!(&self.#field_ident));
+                        body.extend(quote::quote_spanned! {span=> .field(#field_name, #value) });
+                    }
+                    _ => {
+                        let index = syn::Index::from(index);
+                        let value = format_field(&root, field, quote::quote!(&self.#index));
+                        body.extend(quote::quote_spanned! {span=> .field(#value) });
+                    }
+                }
+            }
+
+            quote::quote_spanned! {span=> #body.finish() }
+        }
+    };
+
+    quote::quote_spanned! {span=>
+        impl #impl_generics ::std::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #body
+            }
+        }
+    }
+}
+
+/// Formats `value`, a reference to the field, as `&dyn Debug`.
+///
+/// Secrets are replaced entirely, though an `Option` still shows whether it was set.
+fn format_field(root: &TokenStream, field: &ast::EnvField, value: TokenStream) -> TokenStream {
+    match field.secret {
+        true if utils::is_generic_ty(&field.ty, "Option") => quote::quote! {
+            &(#value).as_ref().map(|_| #root::redact::Redacted)
+        },
+        true => quote::quote! {
+            &#root::redact::Redacted
+        },
+        false => quote::quote! {
+            &#root::redact::MaskUrls(#value)
+        },
+    }
+}
+
+/// Bounds every field type that mentions a type parameter by `Debug`.
+fn add_bounds(mut generics: syn::Generics, data: &ast::EnvData) -> syn::Generics {
+    let params = generics.type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    let fields: Vec<&ast::EnvField> = match data {
+        ast::EnvData::Enum(variants) => variants.iter()
+            .flat_map(|variant| variant.fields.fields.iter())
+            .collect(),
+        ast::EnvData::Struct(fields) => fields.fields.iter().collect(),
+    };
+
+    let predicates = fields.into_iter()
+        .filter(|field| !field.secret && utils::uses_type_param(&field.ty, &params))
+        .map(|field| {
+            let ty = &field.ty;
+            syn::parse_quote!(#ty: ::std::fmt::Debug)
+        })
+        .collect::<Vec<syn::WherePredicate>>();
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
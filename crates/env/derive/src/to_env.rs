@@ -0,0 +1,212 @@
+use darling::util::Override;
+use heck::ToKebabCase;
+use proc_macro2::TokenStream;
+
+use crate::{ast, codegen, utils};
+
+pub fn expand(root: TokenStream, input: ast::DeriveInput) -> TokenStream {
+    let ident = input.ident;
+    let span = ident.span();
+    let generics = codegen::add_bounds(&root, input.generics, &input.data, input.transparent, true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match input.data {
+        ast::EnvData::Enum(variants) => match input.tag {
+            Some(tag) => expand_enum(&root, &tag, variants),
+            None => return quote::quote_spanned! {span=>
+                compile_error!("enums require #[env(tag = \"...\")] to select the variant");
+            },
+        },
+        ast::EnvData::Struct(fields) => {
+            let tuple = fields.style == darling::ast::Style::Tuple;
+            let mut body = quote::quote! {};
+
+            for (index, field) in fields.into_iter().enumerate() {
+                let member = match &field.ident {
+                    Some(field_ident) if !tuple => quote::quote!(#field_ident),
+                    _ => {
+                        let index = syn::Index::from(index);
+                        quote::quote!(#index)
+                    }
+                };
+
+                if input.transparent {
+                    body.extend(quote::quote_spanned! {span=>
+                        #root::ToEnv::write_env(&self.#member, prefix, out);
+                    });
+                    continue;
+                }
+
+                let field_ident = match &field.ident {
+                    Some(field_ident) => field_ident.clone(),
+                    None => proc_macro2::Ident::new(&format!("field_{}", index), span),
+                };
+                body.extend(write_field(&root, &field, quote::quote!(&self.#member), &field_ident, input.rename_all));
+            }
+
+            body
+        }
+    };
+
+    // Mirrors `codegen::scoped`, for `#[env(prefix = "...")]`.
+    let scope = match &input.prefix {
+        Some(type_prefix) => quote::quote_spanned! {span=>
+            let prefix: &str = &format!("{}{}", prefix, #type_prefix);
+        },
+        None => quote::quote! {},
+    };
+
+    quote::quote_spanned! {span=>
+        impl #impl_generics #root::ToEnv for #ident #ty_generics #where_clause {
+            #[allow(unused_variables, clippy::redundant_closure)]
+            fn write_env(&self, prefix: &str, out: &mut std::vec::Vec<(String, String)>) {
+                #scope
+                #body
+            }
+        }
+    }
+}
+
+/// Writes the tag, followed by the payload of the variant.
+fn expand_enum(root: &TokenStream, tag: &syn::LitStr, variants: Vec<ast::EnvVariant>) -> TokenStream {
+    let mut arms = quote::quote! {};
+
+    for variant in variants {
+        let variant_ident = &variant.ident;
+        let span = variant_ident.span();
+
+        let value = match &variant.rename {
+            Some(rename) => rename.value(),
+            None => variant_ident.to_string().to_kebab_case(),
+        };
+        let write_tag = quote::quote_spanned! {span=>
+            out.push((format!("{}{}", prefix, #tag), String::from(#value)));
+        };
+
+        arms.extend(match variant.fields.fields.first() {
+            None => quote::quote_spanned! {span=>
+                Self::#variant_ident => {
+                    #write_tag
+                }
+            },
+            Some(_) => {
+                let variant_prefix = variant.prefix.as_ref().map(syn::LitStr::value).unwrap_or_default();
+                quote::quote_spanned! {span=>
+                    Self::#variant_ident(payload) => {
+                        #write_tag
+                        #root::ToEnv::write_env(payload, &format!("{}{}", prefix, #variant_prefix), out);
+                    }
+                }
+            }
+        });
+    }
+
+    quote::quote! {
+        match self {
+            #arms
+        }
+    }
+}
+
+/// Writes the field, where `value` is a reference to it, mirroring how `codegen::expand_field` reads it.
+fn write_field(
+    root: &TokenStream,
+    field: &ast::EnvField,
+    value: TokenStream,
+    field_ident: &proc_macro2::Ident,
+    rename_all: ast::RenameRule,
+) -> TokenStream {
+    let span = field_ident.span();
+    let ty = &field.ty;
+
+    if let Some(flatten) = &field.flatten {
+        let group_prefix = codegen::flatten_prefix(flatten, field, field_ident, rename_all);
+        if utils::is_generic_ty(ty, "Vec") {
+            return quote::quote_spanned! {span=>
+                for (index, value) in (#value).iter().enumerate() {
+                    #root::ToEnv::write_env(value, &format!("{}{}{}_", prefix, #group_prefix, index), out);
+                }
+            };
+        }
+        if utils::is_generic_ty(ty, "Option") {
+            return quote::quote_spanned! {span=>
+                if let Some(value) = #value {
+                    #root::ToEnv::write_env(value, &format!("{}{}", prefix, #group_prefix), out);
+                }
+            };
+        }
+        return quote::quote_spanned! {span=>
+            #root::ToEnv::write_env(#value, &format!("{}{}", prefix, #group_prefix), out);
+        };
+    }
+
+    let format = format_value(root, field, span);
+
+    if let Some(map_prefix) = &field.prefix_map {
+        return quote::quote_spanned! {span=>
+            for (key, value) in #value {
+                out.push((format!("{}{}{}", prefix, #map_prefix, key), #format));
+            }
+        };
+    }
+
+    let key = codegen::field_key(field, field_ident, rename_all);
+
+    if utils::is_generic_ty(ty, "Option") {
+        // `None` isn't written, which would read back as the default.
+        if let Some(Override::Explicit(default)) = &field.default {
+            return quote::quote_spanned! {default.span()=>
+                compile_error!("`ToEnv` can't write `None` for an `Option` with #[env(default = \"...\")], use `T` or #[env(default)] instead")
+            };
+        }
+        quote::quote_spanned! {span=>
+            if let Some(value) = #value {
+                out.push((format!("{}{}", prefix, #key), #format));
+            }
+        }
+    } else if utils::is_generic_ty(ty, "Vec") {
+        let delimiter = field
+            .delimiter
+            .as_ref()
+            .map(syn::LitStr::value)
+            .unwrap_or(String::from(","));
+        // An empty list is left out when it's also the default, and written as an empty value otherwise.
+        let write = quote::quote_spanned! {span=>
+            out.push((format!("{}{}", prefix, #key), values.join(#delimiter)));
+        };
+        let write = match field.default.is_some() {
+            true => quote::quote_spanned! {span=>
+                if !values.is_empty() {
+                    #write
+                }
+            },
+            false => write,
+        };
+        quote::quote_spanned! {span=>
+            let values = (#value).iter()
+                .map(|value| #format)
+                .collect::<std::vec::Vec<_>>();
+            #write
+        }
+    } else {
+        quote::quote_spanned! {span=>
+            let value = #value;
+            out.push((format!("{}{}", prefix, #key), #format));
+        }
+    }
+}
+
+/// Formats `value`, a reference to the field (or the value inside an `Option`, `Vec` or map).
+fn format_value(root: &TokenStream, field: &ast::EnvField, span: proc_macro2::Span) -> TokenStream {
+    match &field.format_with {
+        Some(format_with) => quote::quote_spanned! {span=>
+            #format_with(value)
+        },
+        None if field.expose_secret => quote::quote_spanned! {span=>
+            #root::ToEnvValue::to_env_value(#root::secrecy::ExposeSecret::expose_secret(value))
+        },
+        None => quote::quote_spanned! {span=>
+            #root::ToEnvValue::to_env_value(value)
+        },
+    }
+}
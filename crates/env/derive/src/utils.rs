@@ -6,6 +6,46 @@ pub(crate) fn is_generic_ty(ty: &syn::Type, name: &str) -> bool {
     subty_if_name(ty, name).is_some()
 }
 
+/// The value type of a `HashMap<String, V>` or `BTreeMap<String, V>`, which may be written with its full path.
+pub(crate) fn map_value_ty(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = strip_group(ty) else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "HashMap" && segment.ident != "BTreeMap" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next()) {
+        (Some(key), Some(value)) if is_string(key) => Some(value),
+        _ => None,
+    }
+}
+
+fn is_string(ty: &syn::Type) -> bool {
+    is_plain_ty(ty, "String")
+}
+
+/// Whether the type is a `PathBuf` or an `OsString`, which are read with `ParseOs`.
+pub(crate) fn is_os_ty(ty: &syn::Type) -> bool {
+    is_plain_ty(ty, "PathBuf") || is_plain_ty(ty, "OsString")
+}
+
+fn is_plain_ty(ty: &syn::Type, name: &str) -> bool {
+    match strip_group(ty) {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()
+            .is_some_and(|segment| segment.ident == name && segment.arguments.is_empty()),
+        _ => false,
+    }
+}
+
 fn subty_if<F>(ty: &syn::Type, f: F) -> Option<&syn::Type>
 where
     F: FnOnce(&syn::PathSegment) -> bool,
@@ -53,3 +93,69 @@ fn last(ty: &syn::Type) -> Option<&syn::PathSegment> {
 fn only_one<I: Iterator<Item = T>, T>(mut it: I) -> Option<T> {
     it.next().filter(|_| it.next().is_none())
 }
+
+/// Whether any of the `params` appear within the type, such as `T` in `Option<T>` or `Vec<T::Item>`.
+pub(crate) fn uses_type_param(ty: &syn::Type, params: &[&syn::Ident]) -> bool {
+    fn visit(tokens: proc_macro2::TokenStream, params: &[&syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => params.iter().any(|param| **param == ident),
+            proc_macro2::TokenTree::Group(group) => visit(group.stream(), params),
+            _ => false,
+        })
+    }
+
+    !params.is_empty() && visit(quote::quote!(#ty), params)
+}
+
+/// Joins the lines of the doc comments, with the leading space of each line removed.
+pub(crate) fn doc_string(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    let doc = lines.join("\n");
+    let doc = doc.trim();
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc.to_string())
+    }
+}
+
+/// Renders the type as it would be written, such as `Option<String>` instead of `Option < String >`.
+pub(crate) fn type_string(ty: &syn::Type) -> String {
+    let tokens = quote::quote!(#ty).to_string();
+
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+    let chars = tokens.chars().collect::<Vec<_>>();
+
+    let mut out = String::with_capacity(tokens.len());
+    for (index, c) in chars.iter().copied().enumerate() {
+        if c == ' ' {
+            let prev = index.checked_sub(1).map(|index| chars[index]);
+            let next = chars.get(index + 1).copied();
+            let keep = match (prev, next) {
+                (Some(prev), Some(next)) => (is_word(prev) && is_word(next)) || prev == ',',
+                _ => false,
+            };
+            if !keep {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
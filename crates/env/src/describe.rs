@@ -0,0 +1,152 @@
+//! Introspection of the variables a [`FromEnv`](crate::FromEnv) type reads.
+
+/// The variables read by a type, as returned by [`FromEnv::describe`](crate::FromEnv::describe).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Description {
+    /// The name of the type.
+    pub ty: &'static str,
+    /// The doc comment of the type.
+    pub doc: Option<&'static str>,
+    pub items: Vec<Item>,
+}
+
+/// A single field (or variant) of a type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    /// The name of the field or variant.
+    pub ident: &'static str,
+    /// The doc comment of the field or variant.
+    pub doc: Option<&'static str>,
+    pub kind: ItemKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemKind {
+    /// A single variable.
+    Var(Var),
+    /// A flattened type.
+    Group(Group),
+    /// A variant of an enum, selected when the tag variable is set to `value`.
+    Variant(Variant),
+    /// Every variable starting with a prefix, collected into a map.
+    Map(Map),
+    /// A list of flattened types, where `<N>` in the prefix of the group stands for the index of each element.
+    List(Group),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Var {
+    /// The full key, with every prefix applied.
+    pub key: String,
+    /// The type of the field, as written.
+    pub ty: &'static str,
+    pub default: Option<DefaultValue>,
+    /// Whether the field is an `Option<_>`.
+    pub optional: bool,
+    /// The delimiter between the values of a `Vec<_>`.
+    pub delimiter: Option<&'static str>,
+    /// Whether `KEY_FILE` is read when `KEY` is missing.
+    pub file_fallback: bool,
+    /// Whether the value is read by `#[env(parse_with = "...")]`, so the type doesn't say which text is accepted.
+    pub parse_with: bool,
+    /// Other full keys the variable is read from, in priority order.
+    pub aliases: Vec<String>,
+    /// Full keys the variable is still read from (after the aliases), with a warning.
+    pub deprecated: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultValue {
+    /// `#[env(default = "...")]`
+    Literal(&'static str),
+    /// `#[env(default)]`
+    Inherit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// The full prefix applied to every variable of the group.
+    pub prefix: String,
+    /// Whether the group is an `Option<_>`, which is only read when any of its variables are set.
+    pub optional: bool,
+    pub description: Description,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    /// The full key of the tag variable.
+    pub key: String,
+    /// The value of the tag variable that selects this variant.
+    pub value: &'static str,
+    /// The payload of the variant, if any.
+    pub group: Option<Group>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Map {
+    /// The full prefix of every variable, which is stripped from the keys of the map.
+    pub prefix: String,
+    /// The type of the values, as written.
+    pub ty: &'static str,
+}
+
+impl Description {
+    /// Prepends `prefix` to every key.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix_keys(prefix);
+        self
+    }
+
+    fn prefix_keys(&mut self, prefix: &str) {
+        if prefix.is_empty() {
+            return;
+        }
+
+        for item in self.items.iter_mut() {
+            match &mut item.kind {
+                ItemKind::Var(var) => {
+                    for key in std::iter::once(&mut var.key).chain(&mut var.aliases).chain(&mut var.deprecated) {
+                        key.insert_str(0, prefix);
+                    }
+                }
+                ItemKind::Map(map) => map.prefix.insert_str(0, prefix),
+                ItemKind::Group(group) | ItemKind::List(group) => group.prefix_keys(prefix),
+                ItemKind::Variant(variant) => {
+                    variant.key.insert_str(0, prefix);
+                    if let Some(group) = &mut variant.group {
+                        group.prefix_keys(prefix);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every variable, including those of nested groups and variants.
+    pub fn vars(&self) -> Vec<&Var> {
+        let mut vars = vec![];
+        self.collect_vars(&mut vars);
+        vars
+    }
+
+    fn collect_vars<'a>(&'a self, vars: &mut Vec<&'a Var>) {
+        for item in self.items.iter() {
+            match &item.kind {
+                ItemKind::Var(var) => vars.push(var),
+                ItemKind::Map(_) => {}
+                ItemKind::Group(group) | ItemKind::List(group) => group.description.collect_vars(vars),
+                ItemKind::Variant(variant) => {
+                    if let Some(group) = &variant.group {
+                        group.description.collect_vars(vars);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Group {
+    fn prefix_keys(&mut self, prefix: &str) {
+        self.prefix.insert_str(0, prefix);
+        self.description.prefix_keys(prefix);
+    }
+}
@@ -0,0 +1,265 @@
+//! Renders the variables of a [`FromEnv`] type as documentation.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::describe::{DefaultValue, Description, ItemKind, Var};
+use crate::FromEnv;
+
+/// Renders a commented `.env.example`, see [`Description::env_example`].
+pub fn env_example<T: FromEnv>() -> String {
+    T::describe().env_example()
+}
+
+/// Renders a Markdown reference table, see [`Description::markdown`].
+pub fn markdown<T: FromEnv>() -> String {
+    T::describe().markdown()
+}
+
+/// A variable along with everything that's needed to document it.
+pub(crate) struct Entry<'a> {
+    /// The variable, or a placeholder standing in for every variable of a map.
+    pub var: Cow<'a, Var>,
+    pub doc: Option<&'a str>,
+    /// The tag values of every variant this variable is nested in.
+    pub conditions: Vec<(&'a str, &'a str)>,
+    /// The values of the tag, if this variable is one.
+    pub choices: Option<Vec<&'static str>>,
+    /// The prefix of every variable, if this stands in for a map.
+    pub prefix_map: Option<&'a str>,
+    /// Whether this variable is read for every element of a list, with `<N>` in the key standing for the index.
+    pub indexed: bool,
+    /// Whether this variable is part of an optional group, which is only read when any of its variables are set.
+    pub optional_group: bool,
+}
+
+impl Description {
+    /// Renders a `.env` file with every variable, along with its doc comment.
+    ///
+    /// Variables with a default literal are filled in, required variables are left blank, and everything else is
+    /// commented out.
+    pub fn env_example(&self) -> String {
+        let mut out = String::new();
+
+        if let Some(doc) = self.doc {
+            push_comment(&mut out, doc);
+            out.push('\n');
+        }
+
+        for (index, entry) in entries(self).into_iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+            }
+
+            let var = &entry.var;
+            if let Some(doc) = entry.doc {
+                push_comment(&mut out, doc);
+            }
+            for note in notes(&entry) {
+                push_comment(&mut out, &note);
+            }
+
+            match (&var.default, var.optional) {
+                (Some(DefaultValue::Literal(default)), _) => {
+                    let _ = writeln!(out, "{}={}", var.key, quote(default));
+                }
+                (Some(DefaultValue::Inherit), _) | (None, true) => {
+                    let _ = writeln!(out, "# {}=", var.key);
+                }
+                (None, false) => {
+                    let _ = writeln!(out, "{}=", var.key);
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Renders a Markdown table with the key, type, default and description of every variable.
+    ///
+    /// Only the first paragraph of each doc comment is used, as code blocks and lists don't fit in a table cell.
+    pub fn markdown(&self) -> String {
+        let mut out = String::from("| Key | Type | Default | Description |\n|-----|------|---------|-------------|\n");
+
+        for entry in entries(self) {
+            let var = &entry.var;
+            let default = match (&var.default, var.optional) {
+                (Some(DefaultValue::Literal(default)), _) => format!("`{}`", default),
+                (Some(DefaultValue::Inherit), _) => String::from("`Default::default()`"),
+                (None, true) => String::from("*optional*"),
+                (None, false) => String::from("**required**"),
+            };
+
+            let mut description = entry.doc.map(first_paragraph).unwrap_or_default();
+            for note in notes(&entry) {
+                if !description.is_empty() {
+                    description.push_str("\n\n");
+                }
+                description.push_str(&note);
+            }
+
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | {} | {} |",
+                var.key,
+                var.ty,
+                default,
+                escape_cell(&description),
+            );
+        }
+
+        out
+    }
+}
+
+pub(crate) fn entries(description: &Description) -> Vec<Entry<'_>> {
+    let mut entries = vec![];
+    collect_entries(description, &[], false, false, &mut entries);
+    entries
+}
+
+fn collect_entries<'a>(
+    description: &'a Description,
+    conditions: &[(&'a str, &'a str)],
+    indexed: bool,
+    optional_group: bool,
+    entries: &mut Vec<Entry<'a>>,
+) {
+    // The tag of an enum is described before its variants.
+    let choices = description.items.iter()
+        .filter_map(|item| match &item.kind {
+            ItemKind::Variant(variant) => Some(variant.value),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for item in description.items.iter() {
+        match &item.kind {
+            ItemKind::Var(var) => entries.push(Entry {
+                var: Cow::Borrowed(var),
+                doc: item.doc,
+                conditions: conditions.to_vec(),
+                choices: if choices.is_empty() {
+                    None
+                } else {
+                    Some(choices.clone())
+                },
+                prefix_map: None,
+                indexed,
+                optional_group,
+            }),
+            ItemKind::Map(map) => entries.push(Entry {
+                var: Cow::Owned(Var {
+                    key: format!("{}<NAME>", map.prefix),
+                    ty: map.ty,
+                    default: None,
+                    optional: true,
+                    delimiter: None,
+                    file_fallback: false,
+                    parse_with: false,
+                    aliases: vec![],
+                    deprecated: vec![],
+                }),
+                doc: item.doc,
+                conditions: conditions.to_vec(),
+                choices: None,
+                prefix_map: Some(&map.prefix),
+                indexed,
+                optional_group,
+            }),
+            ItemKind::Group(group) => {
+                let optional_group = optional_group || group.optional;
+                collect_entries(&group.description, conditions, indexed, optional_group, entries);
+            }
+            ItemKind::List(group) => collect_entries(&group.description, conditions, true, optional_group, entries),
+            ItemKind::Variant(variant) => {
+                if let Some(group) = &variant.group {
+                    let mut conditions = conditions.to_vec();
+                    conditions.push((variant.key.as_str(), variant.value));
+                    collect_entries(&group.description, &conditions, indexed, optional_group, entries);
+                }
+            }
+        }
+    }
+}
+
+/// Additional lines describing how the variable is read.
+pub(crate) fn notes(entry: &Entry<'_>) -> Vec<String> {
+    let mut notes = vec![];
+    if let Some(choices) = &entry.choices {
+        notes.push(format!("One of: {}", choices.join(", ")));
+    }
+    if let Some(prefix) = entry.prefix_map {
+        notes.push(format!("Read from every variable starting with {}", prefix));
+    }
+    if entry.indexed {
+        notes.push(String::from("Read for every index in place of `<N>`, counting up from 0 until one isn't set"));
+    }
+    if entry.optional_group {
+        notes.push(String::from("Part of an optional group, which is only read when any of its variables are set"));
+    }
+    for (key, value) in entry.conditions.iter() {
+        notes.push(format!("Only used when {} is {}", key, value));
+    }
+    if let Some(delimiter) = entry.var.delimiter {
+        notes.push(format!("A list separated by '{}'", delimiter));
+    }
+    if !entry.var.aliases.is_empty() {
+        notes.push(format!("Also read from {}", entry.var.aliases.join(", ")));
+    }
+    if !entry.var.deprecated.is_empty() {
+        notes.push(format!("Deprecated names: {}", entry.var.deprecated.join(", ")));
+    }
+    if entry.var.file_fallback {
+        notes.push(format!("Can also be read from the file named by {}_FILE", entry.var.key));
+    }
+    notes
+}
+
+fn push_comment(out: &mut String, text: &str) {
+    for line in text.lines() {
+        if line.is_empty() {
+            out.push_str("#\n");
+        } else {
+            let _ = writeln!(out, "# {}", line);
+        }
+    }
+}
+
+/// Quotes the value if a `.env` parser wouldn't read it back as is.
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && !value.starts_with(char::is_whitespace)
+        && !value.ends_with(char::is_whitespace)
+        && !value.contains(['#', '"', '\'', '\\', '$', '\n']);
+    if plain {
+        return value.to_string();
+    }
+
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' | '\\' | '$' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The lines of a doc comment up to the first blank line or code fence, joined into one line.
+fn first_paragraph(doc: &str) -> String {
+    doc.lines()
+        .map(str::trim)
+        .take_while(|line| !line.is_empty() && !line.starts_with("```"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+
+use crate::{Error, Origin, Result, Source};
+
+/// The values of a `.env` file.
+///
+/// Supports `export` prefixes, comments, single quoted (literal) and double quoted (escaped) values that can span
+/// multiple lines, as well as `${VAR}` and `${VAR:-default}` expansion.
+///
+/// Expansion looks at the values defined earlier in the file first, and falls back to the process environment.
+/// The process environment is never modified.
+#[derive(Debug, Clone, Default)]
+pub struct DotEnv {
+    values: HashMap<String, Entry>,
+    /// The file the values were read from, if any.
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    value: String,
+    line: usize,
+}
+
+impl DotEnv {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path).map_err(|err| Error::Io {
+            path: path.to_path_buf(),
+            err,
+        })?;
+        let mut dotenv = Self::parse(&input)?;
+        dotenv.path = Some(path.to_path_buf());
+        Ok(dotenv)
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        Parser {
+            chars: input.chars().peekable(),
+            line: 1,
+            values: HashMap::new(),
+        }
+        .parse()
+    }
+
+    /// The line `key` was defined on.
+    pub fn line(&self, key: &str) -> Option<usize> {
+        self.values.get(key).map(|entry| entry.line)
+    }
+}
+
+impl Source for DotEnv {
+    fn get(&self, key: &str) -> Option<Result<&str, &OsStr>> {
+        self.values.get(key).map(|entry| Ok(entry.value.as_str()))
+    }
+
+    fn keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        Some(Box::new(self.values.keys().map(String::as_str)))
+    }
+
+    fn origin(&self, key: &str) -> Origin {
+        Origin::DotEnv {
+            path: self.path.clone(),
+            line: self.line(key),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    values: HashMap<String, Entry>,
+}
+
+impl Parser<'_> {
+    fn parse(mut self) -> Result<DotEnv> {
+        loop {
+            while let Some(c) = self.chars.peek().copied() {
+                if !c.is_whitespace() {
+                    break;
+                }
+                self.next();
+            }
+
+            match self.chars.peek() {
+                None => break,
+                Some('#') => {
+                    self.skip_line();
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            let line = self.line;
+            let mut key = self.parse_key()?;
+            if key == "export" && matches!(self.chars.peek(), Some(' ' | '\t')) {
+                self.skip_inline_whitespace();
+                key = self.parse_key()?;
+            }
+
+            self.skip_inline_whitespace();
+            match self.next() {
+                Some('=') => {}
+                _ => return Err(self.error(format!("expected '=' after '{}'", key))),
+            }
+            let had_whitespace = self.skip_inline_whitespace();
+
+            let value = match self.chars.peek() {
+                Some('\'') => self.parse_single_quoted(line)?,
+                Some('"') => self.parse_double_quoted(line)?,
+                _ => self.parse_unquoted(had_whitespace)?,
+            };
+
+            self.skip_inline_whitespace();
+            match self.chars.peek().copied() {
+                None | Some('\n') => {}
+                Some('#') => self.skip_line(),
+                Some(c) => return Err(self.error(format!("unexpected '{}' after the value of '{}'", c, key))),
+            }
+
+            self.values.insert(key, Entry {
+                value,
+                line,
+            });
+        }
+
+        Ok(DotEnv {
+            values: self.values,
+            path: None,
+        })
+    }
+
+    fn parse_key(&mut self) -> Result<String> {
+        let mut key = String::new();
+        while let Some(c) = self.chars.peek().copied() {
+            if !is_key_char(c) {
+                break;
+            }
+            key.push(c);
+            self.next();
+        }
+
+        if key.is_empty() || key.starts_with(|c: char| c.is_ascii_digit()) {
+            let found = self.chars.peek().copied().unwrap_or(' ');
+            return Err(self.error(format!("invalid key starting with '{}{}'", key, found)));
+        }
+        Ok(key)
+    }
+
+    fn parse_single_quoted(&mut self, line: usize) -> Result<String> {
+        self.next();
+
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err(unterminated(line, '\'')),
+            }
+        }
+    }
+
+    fn parse_double_quoted(&mut self, line: usize) -> Result<String> {
+        self.next();
+
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(c @ ('"' | '\\' | '$')) => value.push(c),
+                    Some(c) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                    None => return Err(unterminated(line, '"')),
+                },
+                Some('$') => self.parse_dollar(&mut value)?,
+                Some(c) => value.push(c),
+                None => return Err(unterminated(line, '"')),
+            }
+        }
+    }
+
+    fn parse_unquoted(&mut self, had_whitespace: bool) -> Result<String> {
+        let mut value = String::new();
+        let mut after_whitespace = had_whitespace;
+
+        while let Some(c) = self.chars.peek().copied() {
+            if c == '\n' || (c == '#' && after_whitespace) {
+                break;
+            }
+            self.next();
+
+            if c == '$' {
+                self.parse_dollar(&mut value)?;
+            } else {
+                value.push(c);
+            }
+            after_whitespace = c.is_whitespace();
+        }
+
+        value.truncate(value.trim_end().len());
+        Ok(value)
+    }
+
+    /// Expands a reference, where the `$` has already been consumed.
+    fn parse_dollar(&mut self, value: &mut String) -> Result<()> {
+        if self.chars.peek() != Some(&'{') {
+            value.push('$');
+            return Ok(());
+        }
+        self.next();
+
+        let line = self.line;
+        let mut name = String::new();
+        while let Some(c) = self.chars.peek().copied() {
+            if !is_key_char(c) {
+                break;
+            }
+            name.push(c);
+            self.next();
+        }
+        if name.is_empty() {
+            return Err(self.error(String::from("expected a variable name after '${'")));
+        }
+
+        let default = match self.next() {
+            Some('}') => None,
+            Some(':') if self.chars.peek() == Some(&'-') => {
+                self.next();
+
+                let mut default = String::new();
+                loop {
+                    match self.next() {
+                        Some('}') => break,
+                        Some('$') => self.parse_dollar(&mut default)?,
+                        Some(c) => default.push(c),
+                        None => return Err(unterminated(line, '}')),
+                    }
+                }
+                Some(default)
+            }
+            Some(c) => return Err(self.error(format!("unexpected '{}' in the expansion of '{}'", c, name))),
+            None => return Err(unterminated(line, '}')),
+        };
+
+        let resolved = match self.values.get(&name) {
+            Some(entry) => Some(entry.value.clone()),
+            None => std::env::var(&name).ok(),
+        };
+
+        match (resolved, default) {
+            (Some(resolved), Some(default)) if resolved.is_empty() => value.push_str(&default),
+            (Some(resolved), _) => value.push_str(&resolved),
+            (None, Some(default)) => value.push_str(&default),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_inline_whitespace(&mut self) -> bool {
+        let mut skipped = false;
+        while let Some(' ' | '\t' | '\r') = self.chars.peek() {
+            self.next();
+            skipped = true;
+        }
+        skipped
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.chars.peek().copied() {
+            if c == '\n' {
+                break;
+            }
+            self.next();
+        }
+    }
+
+    fn error(&self, reason: String) -> Error {
+        Error::DotEnv {
+            line: self.line,
+            reason,
+        }
+    }
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+fn unterminated(line: usize, quote: char) -> Error {
+    Error::DotEnv {
+        line,
+        reason: format!("missing closing '{}'", quote),
+    }
+}
@@ -0,0 +1,169 @@
+//! Parsers for common formats that `FromStr` doesn't cover, each behind a feature.
+//!
+//! The functions are meant for `#[env(parse_with = "...")]`, on fields of the plain type:
+//!
+//! ```ignore
+//! #[derive(FromEnv)]
+//! struct Config {
+//!     #[env(parse_with = "ground_env::formats::duration", default = "30s")]
+//!     timeout: std::time::Duration,
+//!     #[env(parse_with = "ground_env::formats::bool", default)]
+//!     verbose: bool,
+//! }
+//! ```
+//!
+//! [`ByteSize`] and [`Percent`] can also be used as the type of the field directly.
+
+/// The reason a value doesn't match the format.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct FormatError(String);
+
+/// Parses a duration with human units, such as `30s`, `1h 30m` or `250ms`, or a whole number of seconds.
+///
+/// Every unit of [`humantime`] is accepted.
+#[cfg(feature = "duration")]
+pub fn duration(input: &str) -> Result<std::time::Duration, FormatError> {
+    if let Ok(seconds) = input.parse::<u64>() {
+        return Ok(std::time::Duration::from_secs(seconds));
+    }
+    humantime::parse_duration(input).map_err(|err| FormatError(err.to_string()))
+}
+
+#[cfg(feature = "duration")]
+impl crate::ToEnvValue for std::time::Duration {
+    fn to_env_value(&self) -> String {
+        humantime::format_duration(*self).to_string()
+    }
+}
+
+/// Parses a boolean from `true`/`false`, `1`/`0`, `yes`/`no` or `on`/`off`, ignoring case.
+#[cfg(feature = "lenient-bool")]
+pub fn bool(input: &str) -> Result<bool, FormatError> {
+    const TRUE: [&str; 4] = ["true", "1", "yes", "on"];
+    const FALSE: [&str; 4] = ["false", "0", "no", "off"];
+
+    if TRUE.iter().any(|value| value.eq_ignore_ascii_case(input)) {
+        return Ok(true);
+    }
+    if FALSE.iter().any(|value| value.eq_ignore_ascii_case(input)) {
+        return Ok(false);
+    }
+    Err(FormatError(String::from("expected one of: true, false, 1, 0, yes, no, on, off")))
+}
+
+/// Parses a number of bytes, such as `64MiB`, `10kb` or `512`, see [`ByteSize`].
+#[cfg(feature = "byte-size")]
+pub fn byte_size(input: &str) -> Result<u64, FormatError> {
+    input.parse::<ByteSize>().map(|size| size.0)
+}
+
+/// A number of bytes, written as a whole number with an optional unit, such as `64MiB`.
+///
+/// Units are either decimal (`kB`, `MB`, `GB`, `TB`) or binary (`KiB`, `MiB`, `GiB`, `TiB`), ignoring case,
+/// and `B` or no unit for plain bytes.
+#[cfg(feature = "byte-size")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+#[cfg(feature = "byte-size")]
+impl ByteSize {
+    const UNITS: [(&'static str, u64); 9] = [
+        ("TiB", 1 << 40),
+        ("GiB", 1 << 30),
+        ("MiB", 1 << 20),
+        ("KiB", 1 << 10),
+        ("TB", 1_000_000_000_000),
+        ("GB", 1_000_000_000),
+        ("MB", 1_000_000),
+        ("kB", 1_000),
+        ("B", 1),
+    ];
+}
+
+#[cfg(feature = "byte-size")]
+impl std::str::FromStr for ByteSize {
+    type Err = FormatError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+        let (number, unit) = input.split_at(split);
+        let number = number.parse::<u64>()
+            .map_err(|_| FormatError(String::from("expected a whole number, followed by an optional unit")))?;
+
+        let unit = unit.trim_start();
+        let multiplier = match unit.is_empty() {
+            true => 1,
+            false => Self::UNITS.iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+                .map(|(_, multiplier)| *multiplier)
+                .ok_or_else(|| FormatError(format!("unknown unit '{}', expected one of: B, kB, KiB, MB, MiB, GB, GiB, TB, TiB", unit)))?,
+        };
+
+        number.checked_mul(multiplier)
+            .map(ByteSize)
+            .ok_or_else(|| FormatError(String::from("too large")))
+    }
+}
+
+/// Uses the largest binary unit that the size is a multiple of.
+#[cfg(feature = "byte-size")]
+impl std::fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (unit, multiplier) = Self::UNITS.iter()
+            .filter(|(name, _)| name.ends_with("iB"))
+            .find(|(_, multiplier)| self.0 != 0 && self.0.is_multiple_of(*multiplier))
+            .copied()
+            .unwrap_or(("B", 1));
+        write!(f, "{}{}", self.0 / multiplier, unit)
+    }
+}
+
+#[cfg(feature = "byte-size")]
+impl crate::ToEnvValue for ByteSize {
+    fn to_env_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Parses a percentage, such as `75%`, or a fraction, such as `0.75`, see [`Percent`].
+#[cfg(feature = "percent")]
+pub fn percent(input: &str) -> Result<f64, FormatError> {
+    input.parse::<Percent>().map(|percent| percent.0)
+}
+
+/// A fraction, written either as a percentage (`75%`) or as the fraction itself (`0.75`).
+#[cfg(feature = "percent")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Percent(pub f64);
+
+#[cfg(feature = "percent")]
+impl std::str::FromStr for Percent {
+    type Err = FormatError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (number, scale) = match input.strip_suffix('%') {
+            Some(number) => (number.trim_end(), 100.0),
+            None => (input, 1.0),
+        };
+        match number.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Percent(value / scale)),
+            _ => Err(FormatError(String::from("expected a percentage, such as '75%', or a fraction, such as '0.75'"))),
+        }
+    }
+}
+
+#[cfg(feature = "percent")]
+impl std::fmt::Display for Percent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0 * 100.0)
+    }
+}
+
+/// Writes the fraction, as that reads back exactly.
+#[cfg(feature = "percent")]
+impl crate::ToEnvValue for Percent {
+    fn to_env_value(&self) -> String {
+        self.0.to_string()
+    }
+}
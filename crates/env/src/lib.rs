@@ -1,44 +1,95 @@
-pub use ground_env_derive::FromEnv;
+pub use dotenv::DotEnv;
+pub use provenance::Origin;
+pub use ground_env_derive::{EnvEnum, FromEnv, RedactedDebug, ToEnv};
+pub use source::{ProcessEnv, Source};
+pub use to_env::{ToEnv, ToEnvValue};
+pub use unused::UnusedKey;
 use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub mod describe;
+pub mod docs;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod dotenv;
+pub mod formats;
+mod provenance;
+pub mod redact;
+mod source;
+mod to_env;
+mod unused;
+#[doc(hidden)]
+pub mod validate;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "secrecy")]
+#[doc(hidden)]
+pub use secrecy;
+
 pub struct Context {
-    prefix: Vec<&'static str>,
-    env: std::collections::HashMap<String, Result<String, std::ffi::OsString>>,
+    prefix: Vec<Cow<'static, str>>,
+    sources: Vec<Box<dyn Source>>,
+    file_fallback: bool,
+    warnings: Vec<Warning>,
+    /// Every full key that was looked up, see [`Context::unused_keys`].
+    used: BTreeSet<String>,
+    /// Every full prefix that was scanned for a map.
+    used_prefixes: BTreeSet<String>,
+    strict: Vec<String>,
+    /// Where the value of every key that was read came from, see [`Context::provenance`].
+    origins: BTreeMap<String, Origin>,
 }
 
 impl Context {
+    /// Reads from the process environment.
     pub fn env() -> Self {
-        let env = std::env::vars_os()
-            .filter_map(|(key, value)| {
-                // Invalid key => missing key? (Hard to debug if you've messed up the key)
-                let key = key.into_string().ok()?;
-                // Invalid value => we can store the result.
-                let value = value.into_string();
-                Some((key, value))
-            })
-            .collect::<std::collections::HashMap<_, _>>();
+        Self::empty().with_source(ProcessEnv::new())
+    }
 
+    pub fn empty() -> Self {
         Self {
             prefix: vec![],
-            env,
+            sources: vec![],
+            file_fallback: false,
+            warnings: vec![],
+            used: BTreeSet::new(),
+            used_prefixes: BTreeSet::new(),
+            strict: vec![],
+            origins: BTreeMap::new(),
         }
     }
 
-    pub fn empty() -> Self {
+    /// Reads from the given sources, where earlier sources take priority over later ones.
+    pub fn layered<I>(sources: I) -> Self
+        where
+            I: IntoIterator<Item = Box<dyn Source>>,
+    {
         Self {
-            prefix: vec![],
-            env: Default::default(),
+            sources: sources.into_iter().collect(),
+            ..Self::empty()
         }
     }
 
+    /// Adds a source with a lower priority than every source already present.
+    pub fn with_source(mut self, source: impl Source + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
     #[doc(hidden)]
     pub fn with_prefix<T: FromEnv>(&mut self, prefix: &'static str) -> Result<T> {
-        self.prefix.push(prefix);
+        self.scoped(prefix, T::from_ctx)
+    }
 
-        let out = T::from_ctx(self);
+    /// Runs `read` with `prefix` added to the current prefix, for `#[env(prefix = "...")]` on the type itself and for
+    /// every flattened group.
+    #[doc(hidden)]
+    pub fn scoped<R>(&mut self, prefix: impl Into<Cow<'static, str>>, read: impl FnOnce(&mut Self) -> R) -> R {
+        self.prefix.push(prefix.into());
+
+        let out = read(self);
 
         let old = self.prefix.pop();
 
@@ -47,32 +98,371 @@ impl Context {
         out
     }
 
+    /// Reads the elements of a list from `PREFIX_0_`, `PREFIX_1_`, ... until the first index without any variables.
+    ///
+    /// An index is present when any variable described by `T` is set, or any listed key starts with its prefix.
     #[doc(hidden)]
-    pub fn resolve(&self, key: &'static str) -> Result<Result<&str, String>> {
-        let mut key_alloc;
-
-        let key = if self.prefix.is_empty() {
-            Cow::Borrowed(key)
-        } else {
-            let len = self.prefix.iter()
-                .map(|item| item.len())
-                .sum::<usize>()
-                + key.len();
-            key_alloc = String::with_capacity(len);
-            for prefix in self.prefix.iter() {
-                key_alloc.push_str(prefix);
+    pub fn resolve_list<T: FromEnv>(&mut self, prefix: &'static str) -> Result<Vec<T>> {
+        let description = T::describe();
+        let vars = description.vars();
+
+        let mut errors = Errors::default();
+        let mut values = vec![];
+        for index in 0.. {
+            let value = self.scoped(format!("{}{}_", prefix, index), |ctx| {
+                ctx.is_present(&vars, true).then(|| T::from_ctx(ctx))
+            });
+
+            match value {
+                Some(value) => {
+                    if let Some(value) = errors.collect(value) {
+                        values.push(value);
+                    }
+                }
+                None => break,
+            }
+        }
+        errors.finish()?;
+
+        Ok(values)
+    }
+
+    /// Reads an optional group, which is `None` when none of its variables are set.
+    ///
+    /// A group with some of its variables set is an error, listing the variables that are missing.
+    #[doc(hidden)]
+    pub fn resolve_optional<T: FromEnv>(&mut self, prefix: &'static str) -> Result<Option<T>> {
+        let description = T::describe();
+
+        self.scoped(prefix, |ctx| {
+            // Scanning for the prefix alone only makes sense if the group has its own prefix.
+            if !ctx.is_present(&description.vars(), !prefix.is_empty()) {
+                return Ok(None);
             }
-            key_alloc.push_str(key);
-            Cow::Owned(key_alloc)
+
+            T::from_ctx(ctx).map(Some).map_err(|err| {
+                let errors = match err {
+                    Error::Multiple(errors) => errors,
+                    err => vec![err],
+                };
+                let (missing, mut errors) = errors.into_iter()
+                    .partition::<Vec<_>, _>(|err| matches!(err, Error::Missing(_)));
+                if !missing.is_empty() {
+                    errors.insert(0, Error::IncompleteGroup {
+                        prefix: ctx.prefix.concat(),
+                        ty: description.ty,
+                        missing: missing.into_iter()
+                            .filter_map(|err| match err {
+                                Error::Missing(key) => Some(key),
+                                _ => None,
+                            })
+                            .collect(),
+                    });
+                }
+                match errors.len() {
+                    1 => errors.remove(0),
+                    _ => Error::Multiple(errors),
+                }
+            })
+        })
+    }
+
+    /// Whether any of the variables (relative to the current prefix) are set, or (if `scan`) any key starts with the
+    /// current prefix.
+    fn is_present(&self, vars: &[&describe::Var], scan: bool) -> bool {
+        let set = vars.iter()
+            .flat_map(|var| {
+                std::iter::once(&var.key)
+                    .chain(&var.aliases)
+                    .chain(&var.deprecated)
+                    .map(move |key| (key, var.file_fallback))
+            })
+            // Keys of nested lists are placeholders.
+            .filter(|(key, _)| !key.contains('<'))
+            .any(|(key, file_fallback)| {
+                let key = self.prefixed(key);
+                self.lookup(&key).is_some()
+                    || ((file_fallback || self.file_fallback) && self.lookup(&format!("{}_FILE", key)).is_some())
+            });
+        if set || !scan {
+            return set;
+        }
+
+        let prefix = self.prefixed("");
+        self.sources.iter()
+            .filter_map(|source| source.keys())
+            .flatten()
+            .any(|key| key.starts_with(&prefix))
+    }
+
+    /// Reads `KEY_FILE` for every key that is missing, see [`Context::resolve`].
+    pub fn with_file_fallback(mut self) -> Self {
+        self.file_fallback = true;
+        self
+    }
+
+    /// Looks up the value of `key`, where `Ok(Err(key))` means it was missing.
+    ///
+    /// When `file_fallback` is set (or [`Context::with_file_fallback`] was used) and `KEY` is missing, the contents of the
+    /// file named by `KEY_FILE` are used instead (minus a trailing newline), following the Docker/Kubernetes secrets convention.
+    #[doc(hidden)]
+    pub fn resolve(&mut self, key: &'static str, file_fallback: bool) -> Result<Result<String, String>> {
+        match self.resolve_os(key, file_fallback)? {
+            Ok(value) => Ok(Ok(self.unicode(key, value)?)),
+            Err(key) => Ok(Err(key)),
+        }
+    }
+
+    /// Looks up the value of `key` like [`Context::resolve`], but without requiring it to be UTF-8, for [`ParseOs`].
+    #[doc(hidden)]
+    pub fn resolve_os(&mut self, key: &'static str, file_fallback: bool) -> Result<Result<std::ffi::OsString, String>> {
+        let key = self.key(key);
+        self.used.insert(key.to_string());
+
+        let value = self.lookup(&key).map(|value| match value {
+            Ok(value) => std::ffi::OsString::from(value),
+            Err(value) => value.to_os_string(),
+        });
+
+        if value.is_some() {
+            self.origins.insert(key.to_string(), self.source_origin(&key));
+        }
+
+        if !(file_fallback || self.file_fallback) {
+            return Ok(value.ok_or(key.into_owned()));
+        }
+
+        let file_key = format!("{}_FILE", key);
+        self.used.insert(file_key.clone());
+        let path = match self.lookup(&file_key) {
+            Some(path) => std::path::PathBuf::from(match path {
+                Ok(path) => std::ffi::OsStr::new(path),
+                Err(path) => path,
+            }),
+            None => return Ok(value.ok_or(key.into_owned())),
         };
 
-        match self.env.get(key.as_ref()) {
-            Some(t) => match t {
-                Ok(va) => Ok(Ok(va.as_ref())),
-                Err(o) => Err(Error::NotUnicode(key.into_owned(), o.clone())),
-            },
-            None => Ok(Err(key.into_owned())),
+        if value.is_some() {
+            self.origins.remove(&*key);
+            return Err(Error::FileConflict {
+                key: key.into_owned(),
+                file_key,
+            });
+        }
+
+        let mut contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) => return Err(Error::File {
+                key: file_key,
+                path,
+                err,
+            }),
+        };
+        self.origins.insert(key.into_owned(), Origin::SecretFile(path));
+        if contents.ends_with('\n') {
+            contents.pop();
+            if contents.ends_with('\r') {
+                contents.pop();
+            }
+        }
+
+        Ok(Ok(contents.into()))
+    }
+
+    fn unicode(&self, key: &'static str, value: std::ffi::OsString) -> Result<String> {
+        value.into_string()
+            .map_err(|value| Error::NotUnicode(self.key(key).into_owned(), value))
+    }
+
+    /// Reads every variable starting with `prefix` (after the current prefix), keyed by the rest of the key.
+    ///
+    /// Only sources that can list their keys are scanned, but the value of each key is still looked up in priority order.
+    #[doc(hidden)]
+    pub fn resolve_map<T, M>(&mut self, prefix: &'static str) -> Result<M>
+        where
+            T: Parse,
+            M: FromIterator<(String, T)>,
+    {
+        let prefix = self.key(prefix);
+        self.used_prefixes.insert(prefix.to_string());
+
+        let keys = self.sources.iter()
+            .filter_map(|source| source.keys())
+            .flatten()
+            .filter(|key| key.len() > prefix.len() && key.starts_with(&*prefix))
+            .collect::<std::collections::BTreeSet<_>>();
+
+        let mut errors = Errors::default();
+        let mut entries = Vec::with_capacity(keys.len());
+        let mut origins = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = match self.lookup(key) {
+                Some(Ok(value)) => T::parse(value).map_err(|err| with_key(err, key)),
+                Some(Err(value)) => Err(Error::NotUnicode(key.to_string(), value.to_os_string())),
+                None => continue,
+            };
+            if let Some(value) = errors.collect(value) {
+                entries.push((key[prefix.len()..].to_string(), value));
+                origins.push((key.to_string(), self.source_origin(key)));
+            }
+        }
+        self.origins.extend(origins);
+        errors.finish()?;
+
+        Ok(entries.into_iter().collect())
+    }
+
+    /// Looks up the value of `key`, falling back to each of the aliases and then each of the deprecated keys.
+    ///
+    /// Setting more than one of them to different values is an error, and setting a deprecated key records a [`Warning`].
+    #[doc(hidden)]
+    pub fn resolve_aliased(
+        &mut self,
+        key: &'static str,
+        aliases: &[&'static str],
+        deprecated: &[&'static str],
+        file_fallback: bool,
+    ) -> Result<Result<String, String>> {
+        match self.resolve_aliased_os(key, aliases, deprecated, file_fallback)? {
+            Ok(value) => Ok(Ok(self.unicode(key, value)?)),
+            Err(key) => Ok(Err(key)),
+        }
+    }
+
+    /// Looks up the value of `key` like [`Context::resolve_aliased`], but without requiring it to be UTF-8, for [`ParseOs`].
+    #[doc(hidden)]
+    pub fn resolve_aliased_os(
+        &mut self,
+        key: &'static str,
+        aliases: &[&'static str],
+        deprecated: &[&'static str],
+        file_fallback: bool,
+    ) -> Result<Result<std::ffi::OsString, String>> {
+        let mut found: Option<(Cow<'static, str>, std::ffi::OsString)> = None;
+
+        let keys = std::iter::once(&key).chain(aliases).chain(deprecated);
+        for (index, alias) in keys.enumerate() {
+            let Ok(value) = self.resolve_os(alias, file_fallback)? else {
+                continue;
+            };
+
+            if index > aliases.len() {
+                self.warn(Warning::Deprecated {
+                    key: self.key(alias).into_owned(),
+                    replacement: self.key(key).into_owned(),
+                });
+            }
+
+            match &found {
+                Some((found_key, found_value)) => {
+                    if *found_value != value {
+                        return Err(Error::AliasConflict {
+                            key: found_key.clone().into_owned(),
+                            alias: self.key(alias).into_owned(),
+                        });
+                    }
+                }
+                None => found = Some((self.key(alias), value)),
+            }
+        }
+
+        Ok(found.map(|(_, value)| value).ok_or_else(|| self.key(key).into_owned()))
+    }
+
+    /// Everything that was read successfully, but should be fixed, such as deprecated keys.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    fn warn(&mut self, warning: Warning) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("{}", warning);
+
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Names the full key in parse errors, as [`Parse`] doesn't know where its input was read from.
+    #[doc(hidden)]
+    pub fn parsed<T>(&self, key: &'static str, result: Result<T>) -> Result<T> {
+        result.map_err(|err| with_key(err, &self.key(key)))
+    }
+
+    #[doc(hidden)]
+    pub fn invalid(&self, key: &'static str, reason: String) -> Error {
+        Error::Invalid {
+            key: self.key(key).into_owned(),
+            reason,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn invalid_group(&self, ty: &'static str, reason: String) -> Error {
+        Error::InvalidGroup {
+            prefix: self.prefix.concat(),
+            ty,
+            reason,
+        }
+    }
+
+    fn lookup(&self, key: &str) -> Option<Result<&str, &std::ffi::OsStr>> {
+        self.sources.iter()
+            .find_map(|source| source.get(key))
+    }
+
+    /// Resolves the discriminator of an enum, returning the index of the matching tag.
+    #[doc(hidden)]
+    pub fn resolve_tag(&mut self, key: &'static str, tags: &[&'static str]) -> Result<usize> {
+        let value = self.resolve(key, false)?.map_err(Error::Missing)?;
+
+        tags.iter()
+            .position(|tag| tag.eq_ignore_ascii_case(&value))
+            .ok_or_else(|| Error::UnknownTag {
+                key: self.key(key).into_owned(),
+                value,
+                expected: tags.to_vec(),
+            })
+    }
+
+    fn key(&self, key: &'static str) -> Cow<'static, str> {
+        if self.prefix.is_empty() {
+            return Cow::Borrowed(key);
         }
+        Cow::Owned(self.prefixed(key))
+    }
+
+    fn prefixed(&self, key: &str) -> String {
+        let len = self.prefix.iter()
+            .map(|item| item.len())
+            .sum::<usize>()
+            + key.len();
+        let mut key_alloc = String::with_capacity(len);
+        for prefix in self.prefix.iter() {
+            key_alloc.push_str(prefix);
+        }
+        key_alloc.push_str(key);
+        key_alloc
+    }
+}
+
+/// Names the full key in a parse error that doesn't have one yet.
+fn with_key(err: Error, key: &str) -> Error {
+    match err {
+        Error::Parse { key: parse_key, err, input, ty } if parse_key.is_empty() => Error::Parse {
+            key: key.to_string(),
+            err,
+            input,
+            ty,
+        },
+        err => err,
+    }
+}
+
+/// Reads from the pairs written by [`ToEnv::to_env`].
+impl From<Vec<(String, String)>> for Context {
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        Self::empty().with_source(pairs.into_iter().collect::<BTreeMap<_, _>>())
     }
 }
 
@@ -84,12 +474,146 @@ pub enum Error {
     Missing(String),
     #[error("Unable to convert '{0}' into UTF-8")]
     NotUnicode(String, std::ffi::OsString),
-    #[error("Parsing '{input}' as '{ty}' failed: {err}")]
+    #[error("Parsing '{input}'{} as '{ty}' failed: {err}", KeyNote(.key))]
     Parse {
+        /// The full key the input was read from, empty when parsed outside of a [`Context`].
+        key: String,
         err: String,
         input: String,
         ty: &'static str,
     },
+    #[error("Unknown value '{value}' for '{key}', expected one of: {}", .expected.join(", "))]
+    UnknownTag {
+        key: String,
+        value: String,
+        expected: Vec<&'static str>,
+    },
+    #[error("Invalid value for '{key}': {reason}")]
+    Invalid {
+        key: String,
+        reason: String,
+    },
+    #[error("Invalid configuration for '{ty}'{}: {reason}", PrefixNote(.prefix))]
+    InvalidGroup {
+        /// The prefix of every variable of the type.
+        prefix: String,
+        ty: &'static str,
+        reason: String,
+    },
+    #[error("Partially configured '{ty}'{}, missing: {}", PrefixNote(.prefix), .missing.join(", "))]
+    IncompleteGroup {
+        /// The prefix of every variable of the type.
+        prefix: String,
+        ty: &'static str,
+        missing: Vec<String>,
+    },
+    #[error("Unable to parse dotenv file at line {line}: {reason}")]
+    DotEnv {
+        line: usize,
+        reason: String,
+    },
+    #[error("Unable to read '{}': {err}", .path.display())]
+    Io {
+        path: std::path::PathBuf,
+        err: std::io::Error,
+    },
+    #[error("Both '{key}' and '{file_key}' are set")]
+    FileConflict {
+        key: String,
+        file_key: String,
+    },
+    #[error("Both '{key}' and '{alias}' are set, to different values")]
+    AliasConflict {
+        key: String,
+        alias: String,
+    },
+    #[error("Unable to read '{}' from '{key}': {err}", .path.display())]
+    File {
+        key: String,
+        path: std::path::PathBuf,
+        err: std::io::Error,
+    },
+    #[error("{0}")]
+    Unused(UnusedKey),
+    #[error("Found {} configuration errors:{}", .0.len(), ErrorList(.0))]
+    Multiple(Vec<Error>),
+}
+
+/// A problem that didn't prevent reading the configuration, see [`Context::warnings`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Warning {
+    #[error("'{key}' is deprecated, use '{replacement}' instead")]
+    Deprecated {
+        key: String,
+        replacement: String,
+    },
+}
+
+struct PrefixNote<'a>(&'a str);
+
+impl std::fmt::Display for PrefixNote<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, " (with prefix '{}')", self.0)
+    }
+}
+
+struct KeyNote<'a>(&'a str);
+
+impl std::fmt::Display for KeyNote<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, " from '{}'", self.0)
+    }
+}
+
+struct ErrorList<'a>(&'a [Error]);
+
+impl std::fmt::Display for ErrorList<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for err in self.0 {
+            write!(f, "\n  - {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates the errors of every field, so they can be reported together.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct Errors(Vec<Error>);
+
+impl Errors {
+    pub fn collect<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(Error::Multiple(errors)) => {
+                self.0.extend(errors);
+                None
+            }
+            Err(err) => {
+                self.0.push(err);
+                None
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> Result<()> {
+        match self.0.len() {
+            0 => Ok(()),
+            1 => Err(self.0.remove(0)),
+            _ => Err(Error::Multiple(self.0)),
+        }
+    }
+
+    /// Only valid after [`Errors::finish`] has succeeded.
+    pub fn take<T>(value: Option<T>) -> T {
+        value.expect("A field failed without recording an error")
+    }
 }
 
 pub trait FromEnv: Sized {
@@ -97,7 +621,23 @@ pub trait FromEnv: Sized {
         Self::from_ctx(&mut Context::env())
     }
 
+    /// Reads from the process environment, falling back to the values of the given `.env` file.
+    fn from_dotenv(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Self::from_ctx(&mut Context::env().with_source(DotEnv::from_path(path)?))
+    }
+
     fn from_ctx(ctx: &mut Context) -> Result<Self>;
+
+    /// Describes every variable this type reads.
+    ///
+    /// Types that don't describe themselves report no variables.
+    fn describe() -> describe::Description {
+        describe::Description {
+            ty: std::any::type_name::<Self>(),
+            doc: None,
+            items: vec![],
+        }
+    }
 }
 
 pub trait Parse: Sized {
@@ -111,6 +651,7 @@ impl<T, E> Parse for T
 {
     fn parse(value: &str) -> Result<Self> {
         std::str::FromStr::from_str(value).map_err(|err: E| Error::Parse {
+            key: String::new(),
             err: err.to_string(),
             input: value.to_string(),
             ty: std::any::type_name::<Self>(),
@@ -118,6 +659,76 @@ impl<T, E> Parse for T
     }
 }
 
+/// Parses a raw OS value, used instead of [`Parse`] for `PathBuf` and `OsString` fields so any value the OS allows
+/// is accepted, rather than only UTF-8.
+pub trait ParseOs: Sized {
+    fn parse_os(input: &std::ffi::OsStr) -> Result<Self>;
+}
+
+impl ParseOs for std::ffi::OsString {
+    fn parse_os(input: &std::ffi::OsStr) -> Result<Self> {
+        Ok(input.to_os_string())
+    }
+}
+
+impl ParseOs for std::path::PathBuf {
+    fn parse_os(input: &std::ffi::OsStr) -> Result<Self> {
+        Ok(std::path::PathBuf::from(input))
+    }
+}
+
+/// Parses the input with a function, for `#[env(parse_with = "...")]`.
+#[doc(hidden)]
+pub fn parse_with<T, E>(input: &str, parse: impl FnOnce(&str) -> std::result::Result<T, E>) -> Result<T>
+    where
+        E: std::fmt::Display,
+{
+    parse(input).map_err(|err| Error::Parse {
+        key: String::new(),
+        err: err.to_string(),
+        input: input.to_string(),
+        ty: std::any::type_name::<T>(),
+    })
+}
+
+/// Parses the input as `R`, then converts it into `T`, for `#[env(try_from = "...")]`.
+#[doc(hidden)]
+pub fn parse_try_from<R, T>(input: &str) -> Result<T>
+    where
+        R: Parse,
+        T: TryFrom<R>,
+        T::Error: std::fmt::Display,
+{
+    let raw = R::parse(input)?;
+    T::try_from(raw).map_err(|err| Error::Parse {
+        key: String::new(),
+        err: err.to_string(),
+        input: input.to_string(),
+        ty: std::any::type_name::<T>(),
+    })
+}
+
+/// Matches the input case-insensitively against the names of each variant, returning the index of the variant.
+///
+/// The first name of each variant is listed in the error when nothing matches.
+#[doc(hidden)]
+pub fn parse_variant<T>(input: &str, variants: &[&[&'static str]]) -> Result<usize> {
+    variants.iter()
+        .position(|names| names.iter().any(|name| name.eq_ignore_ascii_case(input)))
+        .ok_or_else(|| {
+            let choices = variants.iter()
+                .filter_map(|names| names.first().copied())
+                .collect::<Vec<_>>();
+
+            Error::Parse {
+                key: String::new(),
+                err: format!("expected one of: {}", choices.join(", ")),
+                input: input.to_string(),
+                ty: std::any::type_name::<T>(),
+            }
+        })
+}
+
 #[doc(hidden)]
 pub fn transpose_err<T, E, U>(result: Result<Result<T, U>, E>) -> Result<Result<T, E>, U> {
     match result {
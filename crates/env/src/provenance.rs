@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::Context;
+
+/// Where the value of a key came from, see [`Context::provenance`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// The process environment.
+    Env,
+    /// A `.env` file, along with the line the key was defined on.
+    DotEnv {
+        path: Option<PathBuf>,
+        line: Option<usize>,
+    },
+    /// The file named by `KEY_FILE`.
+    SecretFile(PathBuf),
+    /// `#[env(default = "...")]`
+    Default(&'static str),
+    /// `#[env(default)]`
+    InheritedDefault,
+    /// Any other [`Source`](crate::Source).
+    Other,
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::Env => f.write_str("environment"),
+            Origin::DotEnv { path, line } => {
+                match path {
+                    Some(path) => write!(f, "{}", path.display())?,
+                    None => f.write_str("dotenv")?,
+                }
+                if let Some(line) = line {
+                    write!(f, ":{}", line)?;
+                }
+                Ok(())
+            }
+            Origin::SecretFile(path) => write!(f, "file {}", path.display()),
+            Origin::Default(value) => write!(f, "default \"{}\"", value.escape_debug()),
+            Origin::InheritedDefault => f.write_str("Default::default()"),
+            Origin::Other => f.write_str("custom source"),
+        }
+    }
+}
+
+impl Context {
+    /// Where the value of every key that was read came from, including the keys that fell back to a default.
+    pub fn provenance(&self) -> &BTreeMap<String, Origin> {
+        &self.origins
+    }
+
+    /// Renders [`Context::provenance`] as one `KEY: origin` line per key, such as for logging at startup.
+    pub fn provenance_report(&self) -> String {
+        let mut out = String::new();
+        for (key, origin) in self.origins.iter() {
+            let _ = writeln!(out, "{}: {}", key, origin);
+        }
+        out
+    }
+
+    /// Records that a missing key fell back to a default.
+    #[doc(hidden)]
+    pub fn defaulted<T>(
+        &mut self,
+        value: crate::Result<Result<T, String>>,
+        origin: Origin,
+    ) -> crate::Result<Result<T, String>> {
+        if let Ok(Err(key)) = &value {
+            self.origins.insert(key.clone(), origin);
+        }
+        value
+    }
+
+    pub(crate) fn source_origin(&self, key: &str) -> Origin {
+        self.sources.iter()
+            .find(|source| source.get(key).is_some())
+            .map(|source| source.origin(key))
+            .unwrap_or(Origin::Other)
+    }
+}
@@ -0,0 +1,141 @@
+//! Exports the variables of a [`FromEnv`] type as a JSON Schema.
+
+use serde_json::{json, Map, Value};
+
+use crate::describe::DefaultValue;
+use crate::docs::{entries, notes};
+use crate::FromEnv;
+
+/// Produces an object schema keyed by the environment variables `T` reads.
+///
+/// The standard scalars are typed (`bool`, integers and floats), everything else is a string, as are fields read by
+/// `#[env(parse_with = "...")]`.
+/// Variables without a default that aren't optional are required, unless they're only read by a variant of an enum or are
+/// part of an optional group.
+pub fn json_schema<T: FromEnv>() -> Value {
+    let description = T::describe();
+
+    let mut properties = Map::new();
+    let mut pattern_properties = Map::new();
+    let mut required = vec![];
+
+    for entry in entries(&description) {
+        let var = &entry.var;
+        let ty = match var.parse_with {
+            true => "String",
+            false => scalar_type(var.ty),
+        };
+
+        let mut property = Map::new();
+        match &entry.choices {
+            Some(choices) => {
+                property.insert(String::from("type"), json!("string"));
+                property.insert(String::from("enum"), json!(choices));
+            }
+            None => property.extend(type_schema(if var.delimiter.is_some() { "String" } else { ty })),
+        }
+
+        if let Some(DefaultValue::Literal(default)) = &var.default {
+            property.insert(String::from("default"), default_value(ty, default));
+        }
+
+        let description = entry.doc.map(str::to_string).into_iter()
+            .chain(notes(&entry))
+            .collect::<Vec<_>>();
+        if !description.is_empty() {
+            property.insert(String::from("description"), json!(description.join("\n\n")));
+        }
+
+        if let Some(prefix) = entry.prefix_map {
+            pattern_properties.insert(format!("^{}.+$", key_pattern(prefix)), Value::Object(property));
+            continue;
+        }
+        if entry.indexed {
+            pattern_properties.insert(format!("^{}$", key_pattern(&var.key)), Value::Object(property));
+            continue;
+        }
+
+        if var.default.is_none() && !var.optional && !entry.optional_group && entry.conditions.is_empty() {
+            required.push(var.key.clone());
+        }
+        properties.insert(var.key.clone(), Value::Object(property));
+    }
+
+    let mut schema = Map::new();
+    schema.insert(String::from("$schema"), json!("https://json-schema.org/draft/2020-12/schema"));
+    schema.insert(String::from("title"), json!(description.ty));
+    if let Some(doc) = description.doc {
+        schema.insert(String::from("description"), json!(doc));
+    }
+    schema.insert(String::from("type"), json!("object"));
+    schema.insert(String::from("properties"), Value::Object(properties));
+    if !pattern_properties.is_empty() {
+        schema.insert(String::from("patternProperties"), Value::Object(pattern_properties));
+    }
+    schema.insert(String::from("required"), json!(required));
+
+    Value::Object(schema)
+}
+
+/// Strips `Option<_>` and any leading path, such as `Option<std::primitive::u16>` to `u16`.
+fn scalar_type(ty: &str) -> &str {
+    let ty = ty.strip_prefix("Option<")
+        .and_then(|ty| ty.strip_suffix('>'))
+        .unwrap_or(ty);
+    ty.rsplit("::").next().unwrap_or(ty)
+}
+
+fn type_schema(ty: &str) -> Map<String, Value> {
+    let (ty, min, max): (_, Option<i64>, Option<i64>) = match ty {
+        "bool" => ("boolean", None, None),
+        "u8" => ("integer", Some(0), Some(u8::MAX.into())),
+        "u16" => ("integer", Some(0), Some(u16::MAX.into())),
+        "u32" => ("integer", Some(0), Some(u32::MAX.into())),
+        "u64" | "u128" | "usize" => ("integer", Some(0), None),
+        "i8" => ("integer", Some(i8::MIN.into()), Some(i8::MAX.into())),
+        "i16" => ("integer", Some(i16::MIN.into()), Some(i16::MAX.into())),
+        "i32" => ("integer", Some(i32::MIN.into()), Some(i32::MAX.into())),
+        "i64" | "i128" | "isize" => ("integer", None, None),
+        "f32" | "f64" => ("number", None, None),
+        _ => ("string", None, None),
+    };
+
+    let mut schema = Map::new();
+    schema.insert(String::from("type"), json!(ty));
+    if let Some(min) = min {
+        schema.insert(String::from("minimum"), json!(min));
+    }
+    if let Some(max) = max {
+        schema.insert(String::from("maximum"), json!(max));
+    }
+    schema
+}
+
+/// Types the default literal to match the schema, falling back to a string.
+fn default_value(ty: &str, default: &str) -> Value {
+    let value = match type_schema(ty).get("type").and_then(Value::as_str) {
+        Some("boolean") => default.parse::<bool>().ok().map(Value::from),
+        Some("integer") => default.parse::<i64>().ok().map(Value::from)
+            .or_else(|| default.parse::<u64>().ok().map(Value::from)),
+        Some("number") => default.parse::<f64>().ok().map(Value::from),
+        _ => None,
+    };
+    value.unwrap_or_else(|| json!(default))
+}
+
+/// Escapes the characters of `key` that have a meaning in a regular expression, with every `<N>` matching an index.
+fn key_pattern(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    for (index, part) in key.split("<N>").enumerate() {
+        if index > 0 {
+            out.push_str("[0-9]+");
+        }
+        for c in part.chars() {
+            if "\\.+*?()|[]{}^$".contains(c) {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+    out
+}
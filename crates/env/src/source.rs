@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
+
+use crate::Origin;
+
+/// A place to look up configuration values, such as the process environment.
+///
+/// A [`Context`](crate::Context) consults its sources in priority order, so the first source that knows about a key wins.
+pub trait Source {
+    /// Looks up the raw value of `key`, where `Err` means the value isn't valid UTF-8.
+    fn get(&self, key: &str) -> Option<Result<&str, &OsStr>>;
+
+    /// Lists every key this source knows about.
+    ///
+    /// Sources that can't be enumerated (such as remote secret stores) return `None`, and are skipped by prefix scans.
+    fn keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        None
+    }
+
+    /// Describes where the value of `key` came from, see [`Context::provenance`](crate::Context::provenance).
+    fn origin(&self, key: &str) -> Origin {
+        let _ = key;
+        Origin::Other
+    }
+}
+
+/// A snapshot of the process environment, taken when constructed.
+#[derive(Debug, Clone)]
+pub struct ProcessEnv {
+    env: HashMap<String, Result<String, OsString>>,
+}
+
+impl ProcessEnv {
+    pub fn new() -> Self {
+        let env = std::env::vars_os()
+            .filter_map(|(key, value)| {
+                // Invalid key => missing key? (Hard to debug if you've messed up the key)
+                let key = key.into_string().ok()?;
+                // Invalid value => we can store the result.
+                let value = value.into_string();
+                Some((key, value))
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            env,
+        }
+    }
+}
+
+impl Default for ProcessEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Source for ProcessEnv {
+    fn get(&self, key: &str) -> Option<Result<&str, &OsStr>> {
+        self.env.get(key).map(|value| match value {
+            Ok(value) => Ok(value.as_str()),
+            Err(value) => Err(value.as_os_str()),
+        })
+    }
+
+    fn keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        Some(Box::new(self.env.keys().map(String::as_str)))
+    }
+
+    fn origin(&self, _key: &str) -> Origin {
+        Origin::Env
+    }
+}
+
+impl<S: std::hash::BuildHasher> Source for HashMap<String, String, S> {
+    fn get(&self, key: &str) -> Option<Result<&str, &OsStr>> {
+        HashMap::get(self, key).map(|value| Ok(value.as_str()))
+    }
+
+    fn keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        Some(Box::new(HashMap::keys(self).map(String::as_str)))
+    }
+}
+
+impl Source for BTreeMap<String, String> {
+    fn get(&self, key: &str) -> Option<Result<&str, &OsStr>> {
+        BTreeMap::get(self, key).map(|value| Ok(value.as_str()))
+    }
+
+    fn keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        Some(Box::new(BTreeMap::keys(self).map(String::as_str)))
+    }
+}
+
+impl<T: Source + ?Sized> Source for Box<T> {
+    fn get(&self, key: &str) -> Option<Result<&str, &OsStr>> {
+        (**self).get(key)
+    }
+
+    fn keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        (**self).keys()
+    }
+
+    fn origin(&self, key: &str) -> Origin {
+        (**self).origin(key)
+    }
+}
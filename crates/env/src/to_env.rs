@@ -0,0 +1,73 @@
+use std::ffi::OsString;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::path::PathBuf;
+
+/// Writes a value back into the variables it's read from, the inverse of [`FromEnv`](crate::FromEnv).
+///
+/// Reading the pairs back, such as with `T::from_ctx(&mut Context::from(value.to_env()))`, results in the same value.
+/// Empty `Vec<_>` fields are left out, as there's no way to write an empty list that reads back as one.
+pub trait ToEnv {
+    /// Every `(key, value)` pair of this value.
+    fn to_env(&self) -> Vec<(String, String)> {
+        let mut out = vec![];
+        self.write_env("", &mut out);
+        out
+    }
+
+    /// Appends every `(key, value)` pair of this value to `out`, with `prefix` prepended to each key.
+    fn write_env(&self, prefix: &str, out: &mut Vec<(String, String)>);
+}
+
+/// Formats a single value, the inverse of [`Parse`](crate::Parse).
+///
+/// Unlike [`Parse`], there's no blanket implementation over [`Display`](std::fmt::Display), as `Display` often doesn't
+/// produce something that can be parsed again. Use `#[env(format_with = "...")]` for types that don't implement this.
+pub trait ToEnvValue {
+    fn to_env_value(&self) -> String;
+}
+
+impl<T: ToEnvValue + ?Sized> ToEnvValue for &T {
+    fn to_env_value(&self) -> String {
+        (**self).to_env_value()
+    }
+}
+
+macro_rules! to_string {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ToEnvValue for $ty {
+                fn to_env_value(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+to_string!(
+    String, str, bool, char,
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
+);
+
+/// Paths that aren't valid UTF-8 are converted lossily.
+impl ToEnvValue for PathBuf {
+    fn to_env_value(&self) -> String {
+        self.to_string_lossy().into_owned()
+    }
+}
+
+/// Values that aren't valid UTF-8 are converted lossily.
+impl ToEnvValue for OsString {
+    fn to_env_value(&self) -> String {
+        self.to_string_lossy().into_owned()
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeSet;
+
+use crate::{Context, Error, Errors, FromEnv, Result};
+
+/// A key under a prefix that nothing read, see [`Context::unused_keys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedKey {
+    pub key: String,
+    /// The closest key that was read (or looked for), if it's close enough to be a typo.
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for UnusedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is set, but never read", self.key)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean '{}'?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl Context {
+    /// Rejects keys under `prefix` that nothing read when using [`Context::read`].
+    pub fn with_strict(mut self, prefix: impl Into<String>) -> Self {
+        self.strict.push(prefix.into());
+        self
+    }
+
+    /// Reads `T`, followed by checking the prefixes given to [`Context::with_strict`] for unused keys.
+    pub fn read<T: FromEnv>(&mut self) -> Result<T> {
+        let value = T::from_ctx(self)?;
+
+        let mut errors = Errors::default();
+        for prefix in self.strict.iter() {
+            for unused in self.unused_keys(prefix) {
+                errors.collect::<()>(Err(Error::Unused(unused)));
+            }
+        }
+        errors.finish()?;
+
+        Ok(value)
+    }
+
+    /// Lists the keys starting with `prefix` that nothing has read so far, along with the closest key that was.
+    ///
+    /// Only sources that can list their keys are checked.
+    pub fn unused_keys(&self, prefix: &str) -> Vec<UnusedKey> {
+        let keys = self.sources.iter()
+            .filter_map(|source| source.keys())
+            .flatten()
+            .filter(|key| key.starts_with(prefix))
+            .filter(|key| !self.used.contains(*key))
+            .filter(|key| !self.used_prefixes.iter().any(|used| key.starts_with(used.as_str())))
+            .collect::<BTreeSet<_>>();
+
+        keys.into_iter()
+            .map(|key| UnusedKey {
+                key: key.to_string(),
+                suggestion: self.suggest(key),
+            })
+            .collect()
+    }
+
+    /// The used key with the smallest edit distance, as long as less than a third of the key differs.
+    fn suggest(&self, key: &str) -> Option<String> {
+        let max = (key.chars().count() / 3).max(1);
+
+        self.used.iter()
+            .map(|used| (edit_distance(key, used), used))
+            .filter(|(distance, _)| *distance <= max)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, used)| used.clone())
+    }
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a != *b);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -0,0 +1,63 @@
+//! The checks behind the validation attributes of the derive.
+//!
+//! Each check returns the reason the value is invalid, which is reported as [`Error::Invalid`](crate::Error::Invalid).
+
+use std::fmt::Display;
+
+pub fn min<T: PartialOrd + Display>(value: &T, min: &T) -> Result<(), String> {
+    if value < min {
+        return Err(format!("must be at least {}", min));
+    }
+    Ok(())
+}
+
+pub fn max<T: PartialOrd + Display>(value: &T, max: &T) -> Result<(), String> {
+    if value > max {
+        return Err(format!("must be at most {}", max));
+    }
+    Ok(())
+}
+
+pub fn non_empty(value: &impl AsRef<str>) -> Result<(), String> {
+    if value.as_ref().is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    Ok(())
+}
+
+pub fn one_of<T, C>(value: &T, choices: &[C]) -> Result<(), String>
+    where
+        T: PartialEq<C>,
+        C: Display,
+{
+    if choices.iter().any(|choice| value == choice) {
+        return Ok(());
+    }
+
+    let choices = choices.iter()
+        .map(|choice| choice.to_string())
+        .collect::<Vec<_>>();
+    Err(format!("must be one of: {}", choices.join(", ")))
+}
+
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+pub use regex::Regex;
+
+/// Matches the whole value against `pattern`, compiled into `regex` on first use.
+///
+/// The derive has already checked that the anchored pattern is valid.
+#[cfg(feature = "regex")]
+pub fn regex(value: &impl AsRef<str>, regex: &std::sync::OnceLock<Regex>, pattern: &str) -> Result<(), String> {
+    let regex = regex.get_or_init(|| {
+        Regex::new(&format!("^(?:{})$", pattern)).expect("The pattern was checked by the derive")
+    });
+    if !regex.is_match(value.as_ref()) {
+        return Err(format!("must match '{}'", pattern));
+    }
+    Ok(())
+}
+
+pub fn custom<E: Display>(result: Result<(), E>) -> Result<(), String> {
+    result.map_err(|err| err.to_string())
+}